@@ -53,6 +53,19 @@ export const POKER_ARENA_ABI = [
       { name: "maxAgents", type: "uint8" },
       { name: "sessionLength", type: "uint32" },
       { name: "betDeadline", type: "uint64" },
+      { name: "forfeitPolicy", type: "uint8" },
+      { name: "allowRebuyAfterKick", type: "bool" },
+      { name: "minHands", type: "uint32" },
+      { name: "creatorFeeBps", type: "uint16" },
+      { name: "sessionSecs", type: "uint64" },
+      { name: "tiePolicy", type: "uint8" },
+      { name: "smallBlind", type: "uint256" },
+      { name: "bigBlind", type: "uint256" },
+      { name: "ante", type: "uint256" },
+      { name: "token", type: "address" },
+      { name: "tokenPrizePool", type: "uint256" },
+      { name: "inactivitySecs", type: "uint64" },
+      { name: "kickThreshold", type: "uint8" },
     ],
     outputs: [{ name: "tableId", type: "uint32" }],
   },
@@ -133,6 +146,7 @@ export const POKER_ARENA_ABI = [
       { name: "folded", type: "bool" },
       { name: "kicked", type: "bool" },
       { name: "missedTurns", type: "uint8" },
+      { name: "rebought", type: "uint256" },
     ],
   },
   {
@@ -202,6 +216,10 @@ export const POKER_ARENA_ABI = [
       { name: "tableId", type: "uint32", indexed: true },
       { name: "handNumber", type: "uint32", indexed: false },
       { name: "winningSeat", type: "uint8", indexed: false },
+      { name: "resolution", type: "uint8", indexed: false },
+      { name: "netWon", type: "uint256", indexed: false },
+      { name: "amount", type: "uint256", indexed: false },
+      { name: "chipsAfter", type: "uint256", indexed: false },
     ],
   },
   {
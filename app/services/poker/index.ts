@@ -213,11 +213,33 @@ export async function createTable(mnemonic: string, params: CreateTableParams) {
   const sessionLen = params.sessionLength;
   const betDeadline = BigInt(params.betDeadlineSec);
 
+  // Everything past betDeadline takes the contract default: forfeits to the
+  // prize pool, no rebuy after a kick, no min-hands floor or creator fee,
+  // max time budget, even tie split, no blinds/ante, native currency, and
+  // the global inactivity window / kick threshold.
   const hash = await client.writeContract({
     address: POKER_ARENA_ADDRESS,
     abi: POKER_ARENA_ABI,
     functionName: "createTable",
-    args: [buyIn, maxAgents, sessionLen, betDeadline],
+    args: [
+      buyIn,
+      maxAgents,
+      sessionLen,
+      betDeadline,
+      0, // forfeitPolicy
+      false, // allowRebuyAfterKick
+      0, // minHands
+      0, // creatorFeeBps
+      0n, // sessionSecs
+      0, // tiePolicy
+      0n, // smallBlind
+      0n, // bigBlind
+      0n, // ante
+      "0x0000000000000000000000000000000000000000", // token (native)
+      0n, // tokenPrizePool
+      0n, // inactivitySecs
+      0, // kickThreshold
+    ],
     value: prizePool,
     account,
   });
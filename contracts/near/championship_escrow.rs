@@ -23,7 +23,7 @@ use near_sdk::collections::{LookupMap, UnorderedMap, Vector};
 use near_sdk::json_types::U128;
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
-    env, log, near_bindgen, AccountId, Balance, BorshStorageKey,
+    env, log, near_bindgen, AccountId, Balance, BorshStorageKey, Gas,
     NearToken, PanicOnDefault, Promise,
 };
 
@@ -37,6 +37,19 @@ const BET_PLATFORM_PCT: u128 = 3;
 const MIN_FEE: u128 = 20_000_000_000_000_000_000_000; // 0.02 NEAR (in yocto)
 const MIN_AGENTS: u32 = 3;
 const MIN_VOTE_BALANCE: u128 = 10_000_000_000_000_000_000_000_000; // 10 NEAR
+const MAX_PODIUM: usize = 3; // ranked winner tiers
+const MAX_JUDGE_WINDOW_NS: u64 = 7 * 24 * 3600 * 1_000_000_000; // 7 days
+const DEFAULT_MIN_PHASE_NS: u64 = 1_000_000_000; // 1 second per phase
+const DEFAULT_MAX_HORIZON_NS: u64 = 90 * 24 * 3600 * 1_000_000_000; // 90 days to judge_end
+const MAX_HORIZON_NS: u64 = 365 * 24 * 3600 * 1_000_000_000; // hard cap on max_horizon_ns
+const MAX_ID_LEN: usize = 64; // challenge / agent ids are embedded in storage keys
+const MAX_AGENTS: u32 = 64;
+const STORAGE_RECORD_OVERHEAD: u64 = 40; // protocol's per-record trie charge
+const ENROLL_MIN_GAS: Gas = Gas::from_tgas(10); // comfortably above a max-size enroll
+const REFUND_PCT: u128 = 98;   // % of entry fee returned on withdraw
+const PEEK_FEE_PCT: u128 = 2;  // % of entry fee kept as peek fee (→ platform)
+const DEFAULT_SPONSOR_PLATFORM_PCT: u8 = 5; // platform cut of a sponsor pool
+const MAX_SPONSOR_PLATFORM_PCT: u8 = 20;
 
 // ─── Storage Keys ────────────────────────────────────────────────────
 #[derive(BorshStorageKey, BorshSerialize)]
@@ -51,6 +64,8 @@ enum StorageKey {
     AgentBetPool { challenge_id: String },
     TotalUserBets { challenge_id: String },
     HasClaimed { challenge_id: String },
+    HasWithdrawn { challenge_id: String },
+    Sponsors { challenge_id: String },
 }
 
 // ─── Data Structures ─────────────────────────────────────────────────
@@ -64,10 +79,103 @@ pub struct Challenge {
     pub finalized: bool,
     pub cancelled: bool,
     pub agent_count: u32,
+    pub withdrawn_count: u32,
     pub entry_fee: U128,
     pub total_entry_pool: U128,
     pub total_bet_pool: U128,
     pub winner_agent_id: Option<String>,
+    pub finalize_bounty: U128, // paid to whoever finalizes; refunded on cancel
+    // Added via `sponsor`; paid to the winner owner less the platform cut
+    // (pct snapshotted at create), refunded to sponsors on cancel or when
+    // nobody placed first
+    pub sponsor_pool: U128,
+    pub sponsor_platform_pct: u8,
+    // Everything paid out so far (fees, bounty, claims); never exceeds
+    // entry pool + bet pool + bounty
+    pub total_disbursed: U128,
+    // Per-pool share of the above; each capped at its own pool
+    pub entry_disbursed: U128,
+    pub bet_disbursed: U128,
+    pub sponsor_disbursed: U128,
+    // % of the entry-pool winner share per rank; empty = single winner
+    pub podium_split: Vec<u8>,
+    // Ranked by votes at finalize; ties broken by enrollment order
+    pub ranked_winners: Vec<String>,
+}
+
+/// Lifecycle phase. Each window is inclusive of its end timestamp.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Phase {
+    Enroll,    // now <= enroll_end
+    Compete,   // enroll_end < now <= compete_end
+    Judge,     // compete_end < now <= judge_end
+    Done,      // past judge_end, or finalized
+    Cancelled,
+}
+
+impl Challenge {
+    /// Single source of truth for the window checks in every entry point.
+    pub fn phase(&self, now: u64) -> Phase {
+        if self.cancelled {
+            Phase::Cancelled
+        } else if self.finalized || now > self.judge_end {
+            Phase::Done
+        } else if now > self.compete_end {
+            Phase::Judge
+        } else if now > self.enroll_end {
+            Phase::Compete
+        } else {
+            Phase::Enroll
+        }
+    }
+
+    /// Book an outgoing transfer, split by the pool it is drawn from.
+    /// Last-line safety net: panics rather than let rounding or nested-map
+    /// accounting pay out more than a pool (or the challenge) holds.
+    pub fn disburse(&mut self, entry: u128, bet: u128, sponsor: u128, bounty: u128) {
+        let entry_total = self.entry_disbursed.0 + entry;
+        assert!(entry_total <= self.total_entry_pool.0, "E35: payout exceeds entry pool");
+        let bet_total = self.bet_disbursed.0 + bet;
+        assert!(bet_total <= self.total_bet_pool.0, "E35: payout exceeds bet pool");
+        let sponsor_total = self.sponsor_disbursed.0 + sponsor;
+        assert!(sponsor_total <= self.sponsor_pool.0, "E35: payout exceeds sponsor pool");
+        let total = self.total_disbursed.0 + entry + bet + sponsor + bounty;
+        let cap = self.total_entry_pool.0
+            + self.total_bet_pool.0
+            + self.sponsor_pool.0
+            + self.finalize_bounty.0;
+        assert!(total <= cap, "E35: payout exceeds pools");
+        self.entry_disbursed = U128(entry_total);
+        self.bet_disbursed = U128(bet_total);
+        self.sponsor_disbursed = U128(sponsor_total);
+        self.total_disbursed = U128(total);
+    }
+
+    /// Platform's cut of the sponsor pool; the rest goes to the winner.
+    pub fn sponsor_platform_cut(&self) -> u128 {
+        self.sponsor_pool.0 * self.sponsor_platform_pct as u128 / 100
+    }
+
+    /// Enrolled agents that have not withdrawn.
+    pub fn active_agents(&self) -> u32 {
+        self.agent_count - self.withdrawn_count
+    }
+
+    /// Percent of the entry-pool winner share paid to `rank`. Tiers left
+    /// unfilled (fewer voted agents than podium slots) roll up to first place.
+    pub fn podium_pct(&self, rank: usize) -> u128 {
+        if self.podium_split.is_empty() {
+            return if rank == 0 { 100 } else { 0 };
+        }
+        let mut pct = self.podium_split.get(rank).copied().unwrap_or(0) as u128;
+        if rank == 0 {
+            for p in self.podium_split.iter().skip(self.ranked_winners.len().max(1)) {
+                pct += *p as u128;
+            }
+        }
+        pct
+    }
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -75,6 +183,25 @@ pub struct Challenge {
 pub struct AgentInfo {
     pub owner: AccountId,
     pub enrolled: bool,
+    pub withdrawn: bool,
+}
+
+/// One row of `get_challenge_detail`, in enrollment order.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentDetail {
+    pub agent_id: String,
+    pub owner: AccountId,
+    pub vote_count: u64,
+    pub bet_pool: U128,
+    pub withdrawn: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ChallengeDetail {
+    pub challenge: Challenge,
+    pub agents: Vec<AgentDetail>,
 }
 
 // ─── Contract ────────────────────────────────────────────────────────
@@ -82,6 +209,13 @@ pub struct AgentInfo {
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct ChampionshipEscrow {
     pub platform: AccountId,
+    // Set by `propose_platform`, committed by `accept_platform`
+    pub pending_platform: Option<AccountId>,
+    // Schedule bounds enforced by `create`; platform-tunable
+    pub min_phase_ns: u64,
+    pub max_horizon_ns: u64,
+    // Platform cut of sponsor pools for challenges created from now on
+    pub sponsor_platform_pct: u8,
     pub challenges: UnorderedMap<String, Challenge>,
 
     // Nested maps keyed by "{challenge_id}"
@@ -103,6 +237,10 @@ pub struct ChampionshipEscrow {
     pub total_user_bets: LookupMap<String, LookupMap<AccountId, u128>>,
     // has_claimed[challenge_id] -> LookupMap<AccountId, bool>
     pub has_claimed: LookupMap<String, LookupMap<AccountId, bool>>,
+    // has_withdrawn[challenge_id] -> LookupMap<AccountId, bool>
+    pub has_withdrawn: LookupMap<String, LookupMap<AccountId, bool>>,
+    // sponsors[challenge_id] -> LookupMap<AccountId, u128>
+    pub sponsors: LookupMap<String, LookupMap<AccountId, u128>>,
 }
 
 // ─── Helpers ─────────────────────────────────────────────────────────
@@ -114,32 +252,55 @@ fn now_ns() -> u64 {
     env::block_timestamp() // nanoseconds
 }
 
-#[near_bindgen]
+/// NEP-297 structured event log.
+fn emit_event(event: &str, data: String) {
+    log!(
+        "EVENT_JSON:{{\"standard\":\"championship_escrow\",\"version\":\"1.0.0\",\"event\":\"{}\",\"data\":{}}}",
+        event,
+        data
+    );
+}
+
+/// Ids become part of nested storage keys; keep them non-empty and bounded
+/// so key construction stays unambiguous and cheap.
+fn assert_valid_id(id: &str) {
+    assert!(
+        !id.is_empty() && id.len() <= MAX_ID_LEN,
+        "E25: invalid id"
+    );
+}
+
+/// Upper bound on the bytes one `enroll` adds: the agent_ids slot, the
+/// agents entry, the has_enrolled entry, and (first enroll only, but always
+/// counted) the three top-level map roots. Bounded because ids are capped
+/// at MAX_ID_LEN and account ids at 64 bytes.
+fn enroll_storage_bytes(cid_len: usize, agent_id_len: usize, account_len: usize) -> u64 {
+    let (cid, aid, acct) = (cid_len as u64, agent_id_len as u64, account_len as u64);
+    let prefix = 1 + 4 + cid;                    // borsh(StorageKey)
+    let ids_entry = prefix + 8 + 4 + aid;        // index key + borsh(String)
+    let agent_entry = prefix + 4 + aid + 4 + acct + 2;
+    let enrolled_entry = prefix + 4 + acct + 1;
+    let root = 2 + 4 + cid + 8 + 4 + prefix;     // top-level key + collection header
+    6 * STORAGE_RECORD_OVERHEAD + ids_entry + agent_entry + enrolled_entry + 3 * root
+}
+
+// ─── Internal helpers for nested maps ────────────────────────────────
+// Kept out of the #[near_bindgen] impl so none of them can ever be exposed
+// as a contract method. Every nested collection is keyed by a `StorageKey`
+// variant, whose Borsh encoding is `[variant_tag][u32 len LE][challenge_id]`.
+// The explicit length prefix means two ids that share a prefix ("c1" vs
+// "c10") always land in disjoint storage regions, and the one-byte variant
+// tags (0..=11) can never collide with the ASCII top-level prefixes below.
 impl ChampionshipEscrow {
-    // ─── Init ────────────────────────────────────────────────────────
-    #[init]
-    pub fn new(platform: AccountId) -> Self {
-        assert!(!env::is_x86_feature_detected!("") || true); // no-op, always true
-        assert!(
-            env::is_valid_account_id(platform.as_bytes()),
-            "E1: invalid platform"
-        );
-        Self {
-            platform,
-            challenges: UnorderedMap::new(StorageKey::Challenges),
-            agent_ids: LookupMap::new(b"ai"),
-            agents: LookupMap::new(b"ag"),
-            has_enrolled: LookupMap::new(b"he"),
-            has_voted: LookupMap::new(b"hv"),
-            vote_count: LookupMap::new(b"vc"),
-            bets: LookupMap::new(b"bt"),
-            agent_bet_pool: LookupMap::new(b"ap"),
-            total_user_bets: LookupMap::new(b"tu"),
-            has_claimed: LookupMap::new(b"hc"),
-        }
+    /// Read one entry of a nested map without creating the inner map.
+    fn nested_get<K, V>(maps: &LookupMap<String, LookupMap<K, V>>, id: &str, key: &K) -> Option<V>
+    where
+        K: BorshSerialize,
+        V: BorshSerialize + BorshDeserialize,
+    {
+        maps.get(&id.to_string()).and_then(|m| m.get(key))
     }
 
-    // ─── Internal helpers for nested maps ────────────────────────────
     fn get_or_create_agent_ids(&mut self, cid: &str) -> Vector<String> {
         match self.agent_ids.get(&cid.to_string()) {
             Some(v) => v,
@@ -228,7 +389,61 @@ impl ChampionshipEscrow {
         }
     }
 
+    fn get_or_create_has_withdrawn(
+        &mut self, cid: &str
+    ) -> LookupMap<AccountId, bool> {
+        match self.has_withdrawn.get(&cid.to_string()) {
+            Some(m) => m,
+            None => LookupMap::new(
+                StorageKey::HasWithdrawn { challenge_id: cid.to_string() }
+            ),
+        }
+    }
+
+    fn get_or_create_sponsors(&mut self, cid: &str) -> LookupMap<AccountId, u128> {
+        match self.sponsors.get(&cid.to_string()) {
+            Some(m) => m,
+            None => LookupMap::new(
+                StorageKey::Sponsors { challenge_id: cid.to_string() }
+            ),
+        }
+    }
+}
+
+#[near_bindgen]
+impl ChampionshipEscrow {
+    // ─── Init ────────────────────────────────────────────────────────
+    #[init]
+    pub fn new(platform: AccountId) -> Self {
+        assert!(!env::is_x86_feature_detected!("") || true); // no-op, always true
+        assert!(
+            env::is_valid_account_id(platform.as_bytes()),
+            "E1: invalid platform"
+        );
+        Self {
+            platform,
+            pending_platform: None,
+            min_phase_ns: DEFAULT_MIN_PHASE_NS,
+            max_horizon_ns: DEFAULT_MAX_HORIZON_NS,
+            sponsor_platform_pct: DEFAULT_SPONSOR_PLATFORM_PCT,
+            challenges: UnorderedMap::new(StorageKey::Challenges),
+            agent_ids: LookupMap::new(b"ai"),
+            agents: LookupMap::new(b"ag"),
+            has_enrolled: LookupMap::new(b"he"),
+            has_voted: LookupMap::new(b"hv"),
+            vote_count: LookupMap::new(b"vc"),
+            bets: LookupMap::new(b"bt"),
+            agent_bet_pool: LookupMap::new(b"ap"),
+            total_user_bets: LookupMap::new(b"tu"),
+            has_claimed: LookupMap::new(b"hc"),
+            has_withdrawn: LookupMap::new(b"hw"),
+            sponsors: LookupMap::new(b"sp"),
+        }
+    }
+
     // ─── Create ──────────────────────────────────────────────────────
+    /// Any attached deposit becomes the finalize bounty.
+    #[payable]
     pub fn create(
         &mut self,
         id: String,
@@ -236,7 +451,9 @@ impl ChampionshipEscrow {
         enroll_end: u64,
         compete_end: u64,
         judge_end: u64,
+        podium_split: Option<Vec<u8>>,
     ) {
+        assert_valid_id(&id);
         assert!(
             self.challenges.get(&id).is_none(),
             "E2: challenge exists"
@@ -247,6 +464,28 @@ impl ChampionshipEscrow {
         assert!(enroll_end > now, "E4: bad enroll_end");
         assert!(compete_end > enroll_end, "E4: bad compete_end");
         assert!(judge_end > compete_end, "E4: bad judge_end");
+        assert!(
+            judge_end - compete_end <= MAX_JUDGE_WINDOW_NS,
+            "E4: judging window too long"
+        );
+        assert!(
+            compete_end - enroll_end >= self.min_phase_ns
+                && judge_end - compete_end >= self.min_phase_ns,
+            "E4: phase too short"
+        );
+        assert!(
+            judge_end - now <= self.max_horizon_ns,
+            "E4: schedule too far out"
+        );
+
+        let podium_split = podium_split.unwrap_or_default();
+        assert!(podium_split.len() <= MAX_PODIUM, "E28: bad podium split");
+        if !podium_split.is_empty() {
+            let total: u32 = podium_split.iter().map(|&p| p as u32).sum();
+            assert!(total == 100, "E28: bad podium split");
+        }
+
+        let bounty = env::attached_deposit().as_yoctonear();
 
         let c = Challenge {
             creator: env::predecessor_account_id(),
@@ -256,32 +495,44 @@ impl ChampionshipEscrow {
             finalized: false,
             cancelled: false,
             agent_count: 0,
+            withdrawn_count: 0,
             entry_fee: fee,
             total_entry_pool: U128(0),
             total_bet_pool: U128(0),
             winner_agent_id: None,
+            finalize_bounty: U128(bounty),
+            sponsor_pool: U128(0),
+            sponsor_platform_pct: self.sponsor_platform_pct,
+            total_disbursed: U128(0),
+            entry_disbursed: U128(0),
+            bet_disbursed: U128(0),
+            sponsor_disbursed: U128(0),
+            podium_split,
+            ranked_winners: Vec::new(),
         };
 
         self.challenges.insert(&id, &c);
 
         log!(
-            "Created challenge {} by {} fee={} enroll_end={} compete_end={} judge_end={}",
+            "Created challenge {} by {} fee={} enroll_end={} compete_end={} judge_end={} bounty={}",
             id,
             c.creator,
             fee.0,
             enroll_end,
             compete_end,
-            judge_end
+            judge_end,
+            bounty
         );
     }
 
     // ─── Enroll ──────────────────────────────────────────────────────
     #[payable]
     pub fn enroll(&mut self, id: String, agent_id: String) {
+        assert_valid_id(&agent_id);
         let mut c = self.challenges.get(&id).expect("E5: not found");
-        let now = now_ns();
-        assert!(now <= c.enroll_end, "E6: enrollment ended");
-        assert!(!c.cancelled, "E7: cancelled");
+        let phase = c.phase(now_ns());
+        assert!(phase != Phase::Cancelled, "E7: cancelled");
+        assert!(phase == Phase::Enroll, "E6: enrollment ended");
         assert!(
             NearToken::from_yoctonear(env::attached_deposit().as_yoctonear())
                 == NearToken::from_yoctonear(c.entry_fee.0),
@@ -290,6 +541,20 @@ impl ChampionshipEscrow {
 
         let caller = env::predecessor_account_id();
 
+        // Fail up front, before any write, if this enroll can't complete
+        assert!(c.agent_count < MAX_AGENTS, "E32: max agents");
+        assert!(
+            env::prepaid_gas().saturating_sub(env::used_gas()) >= ENROLL_MIN_GAS,
+            "E34: attach at least 10 Tgas"
+        );
+        let added = enroll_storage_bytes(id.len(), agent_id.len(), caller.as_str().len());
+        let needed = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage() + added) as u128);
+        assert!(
+            env::account_balance() >= needed,
+            "E33: contract balance can't cover enroll storage"
+        );
+
         // Check has_enrolled
         let mut enrolled_map = self.get_or_create_has_enrolled(&id);
         assert!(
@@ -310,6 +575,7 @@ impl ChampionshipEscrow {
             &AgentInfo {
                 owner: caller.clone(),
                 enrolled: true,
+                withdrawn: false,
             },
         );
         self.agents.insert(&id.to_string(), &agents_map);
@@ -341,22 +607,16 @@ impl ChampionshipEscrow {
     pub fn bet(&mut self, id: String, agent_id: String) {
         let mut c = self.challenges.get(&id).expect("E5: not found");
         assert!(!c.cancelled && !c.finalized, "E11: not active");
-        assert!(c.agent_count >= MIN_AGENTS, "E12: too few agents");
+        assert!(c.active_agents() >= MIN_AGENTS, "E12: too few agents");
 
-        let now = now_ns();
-        assert!(
-            now > c.enroll_end && now <= c.compete_end,
-            "E13: wrong phase"
-        );
+        assert!(c.phase(now_ns()) == Phase::Compete, "E13: wrong phase");
 
         let agents_map = self.get_or_create_agents(&id);
-        assert!(
-            agents_map
-                .get(&agent_id)
-                .map(|a| a.enrolled)
-                .unwrap_or(false),
-            "E14: agent not enrolled"
-        );
+        let agent = agents_map
+            .get(&agent_id)
+            .filter(|a| a.enrolled)
+            .expect("E14: agent not enrolled");
+        assert!(!agent.withdrawn, "E29: agent withdrawn");
 
         let caller = env::predecessor_account_id();
         assert!(caller != c.creator, "E15: creator cannot bet");
@@ -400,22 +660,16 @@ impl ChampionshipEscrow {
     pub fn vote(&mut self, id: String, agent_id: String) {
         let c = self.challenges.get(&id).expect("E5: not found");
         assert!(!c.cancelled && !c.finalized, "E11: not active");
-        assert!(c.agent_count >= MIN_AGENTS, "E12: too few agents");
+        assert!(c.active_agents() >= MIN_AGENTS, "E12: too few agents");
 
-        let now = now_ns();
-        assert!(
-            now > c.compete_end && now <= c.judge_end,
-            "E13: wrong phase"
-        );
+        assert!(c.phase(now_ns()) == Phase::Judge, "E13: wrong phase");
 
         let agents_map = self.get_or_create_agents(&id);
-        assert!(
-            agents_map
-                .get(&agent_id)
-                .map(|a| a.enrolled)
-                .unwrap_or(false),
-            "E14: agent not enrolled"
-        );
+        let agent = agents_map
+            .get(&agent_id)
+            .filter(|a| a.enrolled)
+            .expect("E14: agent not enrolled");
+        assert!(!agent.withdrawn, "E29: agent withdrawn");
 
         let caller = env::predecessor_account_id();
 
@@ -446,14 +700,97 @@ impl ChampionshipEscrow {
         );
     }
 
+    // ─── Withdraw ────────────────────────────────────────────────────
+    /// Agent owner pulls out after enrollment closes: 98% of the entry fee
+    /// back to the caller, 2% peek fee to the platform. Not once anyone has
+    /// bet on the agent — those bets could then never win or be refunded.
+    pub fn withdraw(&mut self, id: String, agent_id: String) -> Promise {
+        let mut c = self.challenges.get(&id).expect("E5: not found");
+        assert!(!c.finalized && !c.cancelled, "E11: not active");
+
+        assert!(c.phase(now_ns()) == Phase::Compete, "E13: wrong phase");
+
+        let caller = env::predecessor_account_id();
+        let mut agents_map = self.get_or_create_agents(&id);
+        let mut agent = agents_map
+            .get(&agent_id)
+            .filter(|a| a.enrolled)
+            .expect("E14: agent not enrolled");
+        assert!(agent.owner == caller, "E30: not agent owner");
+        assert!(!agent.withdrawn, "E31: already withdrawn");
+        assert!(
+            Self::nested_get(&self.agent_bet_pool, &id, &agent_id).unwrap_or(0) == 0,
+            "E38: agent has bets"
+        );
+
+        agent.withdrawn = true;
+        agents_map.insert(&agent_id, &agent);
+        self.agents.insert(&id.to_string(), &agents_map);
+
+        let mut withdrawn_map = self.get_or_create_has_withdrawn(&id);
+        withdrawn_map.insert(&caller, &true);
+        self.has_withdrawn.insert(&id.to_string(), &withdrawn_map);
+
+        c.withdrawn_count += 1;
+        c.total_entry_pool = U128(c.total_entry_pool.0 - c.entry_fee.0);
+        self.challenges.insert(&id, &c);
+
+        let refund = c.entry_fee.0 * REFUND_PCT / 100;
+        let peek_fee = c.entry_fee.0 * PEEK_FEE_PCT / 100;
+
+        log!(
+            "Agent {} withdrew from challenge {} refund={} peek_fee={}",
+            agent_id,
+            id,
+            refund,
+            peek_fee
+        );
+
+        Promise::new(caller)
+            .transfer(NearToken::from_yoctonear(refund))
+            .and(
+                Promise::new(self.platform.clone())
+                    .transfer(NearToken::from_yoctonear(peek_fee)),
+            )
+    }
+
+    // ─── Sponsor ─────────────────────────────────────────────────────
+    /// Anyone may boost the winner's prize until judging ends. Refunded to
+    /// the sponsor if the challenge is cancelled.
+    #[payable]
+    pub fn sponsor(&mut self, id: String) {
+        let mut c = self.challenges.get(&id).expect("E5: not found");
+        assert!(!c.finalized && !c.cancelled, "E11: not active");
+        assert!(c.phase(now_ns()) != Phase::Done, "E13: wrong phase");
+
+        let deposit = env::attached_deposit().as_yoctonear();
+        assert!(deposit > 0, "E36: zero sponsorship");
+
+        let caller = env::predecessor_account_id();
+        let mut sponsors = self.get_or_create_sponsors(&id);
+        let prev = sponsors.get(&caller).unwrap_or(0);
+        sponsors.insert(&caller, &(prev + deposit));
+        self.sponsors.insert(&id.to_string(), &sponsors);
+
+        c.sponsor_pool = U128(c.sponsor_pool.0 + deposit);
+        self.challenges.insert(&id, &c);
+
+        emit_event(
+            "sponsored",
+            format!(
+                "{{\"id\":\"{}\",\"sponsor\":\"{}\",\"amount\":\"{}\",\"sponsor_pool\":\"{}\"}}",
+                id, caller, deposit, c.sponsor_pool.0
+            ),
+        );
+    }
+
     // ─── Cancel ──────────────────────────────────────────────────────
     pub fn cancel(&mut self, id: String) {
         let mut c = self.challenges.get(&id).expect("E5: not found");
         assert!(!c.finalized && !c.cancelled, "E11: not active");
 
-        let now = now_ns();
-        assert!(now > c.enroll_end, "E19: enrollment not ended");
-        assert!(c.agent_count < MIN_AGENTS, "E20: enough agents");
+        assert!(c.phase(now_ns()) != Phase::Enroll, "E19: enrollment not ended");
+        assert!(c.active_agents() < MIN_AGENTS, "E20: enough agents");
 
         c.cancelled = true;
         self.challenges.insert(&id, &c);
@@ -465,35 +802,62 @@ impl ChampionshipEscrow {
     pub fn finalize(&mut self, id: String) -> Promise {
         let mut c = self.challenges.get(&id).expect("E5: not found");
         assert!(!c.finalized && !c.cancelled, "E11: not active");
-        assert!(now_ns() > c.judge_end, "E19: judging not ended");
-        assert!(c.agent_count >= MIN_AGENTS, "E12: too few agents");
+        assert!(c.phase(now_ns()) == Phase::Done, "E19: judging not ended");
+
+        // Withdrawals left too few agents (possibly none) to crown anyone:
+        // settle as a cancel so every claim becomes a refund
+        if c.active_agents() < MIN_AGENTS {
+            c.cancelled = true;
+            self.challenges.insert(&id, &c);
+            log!("Cancelled challenge {} at finalize: too few active agents", id);
+            return Promise::new(env::current_account_id())
+                .transfer(NearToken::from_yoctonear(0));
+        }
 
         // Find winner
         let ids_vec = self.get_or_create_agent_ids(&id);
+        let agents_map = self.get_or_create_agents(&id);
         let vc = self.get_or_create_vote_count(&id);
         let len = ids_vec.len();
 
-        let mut winner: Option<String> = None;
-        let mut max_votes: u64 = 0;
-
+        // Rank every still-enrolled agent that received votes. The sort is
+        // stable, so agents tied on votes keep enrollment order — the
+        // earlier enrollee wins a tie at any cutoff.
+        let mut ranked: Vec<(String, u64)> = Vec::new();
         for i in 0..len {
             let aid = ids_vec.get(i).unwrap();
+            let active = agents_map
+                .get(&aid)
+                .map_or(false, |a| a.enrolled && !a.withdrawn);
+            if !active {
+                continue;
+            }
             let votes = vc.get(&aid).unwrap_or(0);
-            if votes > max_votes {
-                max_votes = votes;
-                winner = Some(aid);
+            if votes > 0 {
+                ranked.push((aid, votes));
             }
         }
+        ranked.sort_by(|a, b| b.1.cmp(&a.1));
+        ranked.truncate(c.podium_split.len().max(1));
 
-        let winner_id = winner.unwrap_or_default();
+        let winner_id = ranked.first().map(|(a, _)| a.clone()).unwrap_or_default();
+        c.ranked_winners = ranked.into_iter().map(|(a, _)| a).collect();
         c.winner_agent_id = Some(winner_id.clone());
         c.finalized = true;
-        self.challenges.insert(&id, &c);
 
-        // Platform fee
-        let platform_fee = (c.total_entry_pool.0 * ENTRY_PLATFORM_PCT
-            + c.total_bet_pool.0 * BET_PLATFORM_PCT)
-            / 100;
+        // Platform fee, rounded per pool like every other share
+        let entry_fee = c.total_entry_pool.0 * ENTRY_PLATFORM_PCT / 100;
+        let bet_fee = c.total_bet_pool.0 * BET_PLATFORM_PCT / 100;
+        // No first place (nobody got a vote): sponsors reclaim their
+        // deposits in full at claim, so the platform takes no cut
+        let sponsor_fee = if c.ranked_winners.is_empty() {
+            0
+        } else {
+            c.sponsor_platform_cut()
+        };
+        let platform_fee = entry_fee + bet_fee + sponsor_fee;
+        c.disburse(entry_fee, bet_fee, sponsor_fee, c.finalize_bounty.0);
+        self.challenges.insert(&id, &c);
 
         log!(
             "Finalized challenge {} winner={} platform_fee={}",
@@ -503,19 +867,36 @@ impl ChampionshipEscrow {
         );
 
         // Transfer platform fee
-        if platform_fee > 0 {
+        let fee_promise = if platform_fee > 0 {
             Promise::new(self.platform.clone())
                 .transfer(NearToken::from_yoctonear(platform_fee))
         } else {
             // Return a self-call promise as no-op
             Promise::new(env::current_account_id())
                 .transfer(NearToken::from_yoctonear(0))
+        };
+
+        // Finalize bounty goes to the caller, separate from the pools
+        let bounty = c.finalize_bounty.0;
+        if bounty > 0 {
+            fee_promise.and(
+                Promise::new(env::predecessor_account_id())
+                    .transfer(NearToken::from_yoctonear(bounty)),
+            )
+        } else {
+            fee_promise
         }
     }
 
     // ─── Claim ───────────────────────────────────────────────────────
+    /// Concurrency: every call to this contract runs as its own receipt,
+    /// and receipts for one account execute strictly one after another,
+    /// each committing state before the next starts — two claims in the
+    /// same block still see each other's `has_claimed` write. The transfer
+    /// Promise is only scheduled here and runs in a later receipt, after
+    /// the flag is stored, so no nonce or lock is needed.
     pub fn claim(&mut self, id: String) -> Promise {
-        let c = self.challenges.get(&id).expect("E5: not found");
+        let mut c = self.challenges.get(&id).expect("E5: not found");
         assert!(c.finalized || c.cancelled, "E22: not done");
 
         let caller = env::predecessor_account_id();
@@ -526,33 +907,62 @@ impl ChampionshipEscrow {
             "E23: already claimed"
         );
 
-        let mut amt: u128 = 0;
+        // Tracked per source pool for `disburse`
+        let mut from_entry: u128 = 0;
+        let mut from_bets: u128 = 0;
+        let mut from_sponsor: u128 = 0;
+        let mut from_bounty: u128 = 0;
 
         if c.cancelled {
-            // Refund entry fee if enrolled
+            // Refund entry fee if enrolled — unless withdraw already
+            // returned it (less the peek fee)
             let enrolled_map = self.get_or_create_has_enrolled(&id);
-            if enrolled_map.get(&caller).unwrap_or(false) {
-                amt += c.entry_fee.0;
+            let withdrawn_map = self.get_or_create_has_withdrawn(&id);
+            if enrolled_map.get(&caller).unwrap_or(false)
+                && !withdrawn_map.get(&caller).unwrap_or(false)
+            {
+                from_entry += c.entry_fee.0;
             }
             // Refund bets
             let tub = self.get_or_create_total_user_bets(&id);
-            amt += tub.get(&caller).unwrap_or(0);
+            from_bets += tub.get(&caller).unwrap_or(0);
+            // Unused finalize bounty back to the creator
+            if caller == c.creator {
+                from_bounty += c.finalize_bounty.0;
+            }
+            // Sponsorships back to their sponsors
+            let sponsors = self.get_or_create_sponsors(&id);
+            from_sponsor += sponsors.get(&caller).unwrap_or(0);
         } else {
             // Finalized
             let winner_id = c.winner_agent_id.clone().unwrap_or_default();
 
-            // Winner agent owner gets 95% of entry pool
+            // Ranked winner owners share 95% of entry pool per podium tier
+            let winner_share = (c.total_entry_pool.0 * ENTRY_WINNER_PCT) / 100;
             let agents_map = self.get_or_create_agents(&id);
-            if let Some(agent_info) = agents_map.get(&winner_id) {
+            for (rank, aid) in c.ranked_winners.iter().enumerate() {
+                if let Some(agent_info) = agents_map.get(aid) {
+                    if agent_info.owner == caller {
+                        from_entry += winner_share * c.podium_pct(rank) / 100;
+                    }
+                }
+            }
+
+            // First-place owner takes the sponsor pool less the platform cut;
+            // with no first place, sponsorships go back as on cancel
+            if let Some(agent_info) = c.ranked_winners.first().and_then(|a| agents_map.get(a)) {
                 if agent_info.owner == caller {
-                    amt += (c.total_entry_pool.0 * ENTRY_WINNER_PCT) / 100;
+                    from_sponsor += c.sponsor_pool.0 - c.sponsor_platform_cut();
                 }
+            } else if c.ranked_winners.is_empty() {
+                let sponsors = self.get_or_create_sponsors(&id);
+                from_sponsor += sponsors.get(&caller).unwrap_or(0);
             }
 
             // Creator gets 4% entry + 2% bets
             if caller == c.creator {
-                amt += (c.total_entry_pool.0 * ENTRY_CREATOR_PCT) / 100;
-                amt += (c.total_bet_pool.0 * BET_CREATOR_PCT) / 100;
+                from_entry += (c.total_entry_pool.0 * ENTRY_CREATOR_PCT) / 100;
+                from_bets += (c.total_bet_pool.0 * BET_CREATOR_PCT) / 100;
             }
 
             // Winning bettors share 95% of bet pool
@@ -563,14 +973,17 @@ impl ChampionshipEscrow {
                 let abp = self.get_or_create_agent_bet_pool(&id);
                 let total_winner_pool = abp.get(&winner_id).unwrap_or(0);
                 if total_winner_pool > 0 {
-                    amt += ((c.total_bet_pool.0 * BET_WINNER_PCT) / 100
+                    from_bets += ((c.total_bet_pool.0 * BET_WINNER_PCT) / 100
                         * user_bet_on_winner)
                         / total_winner_pool;
                 }
             }
         }
 
+        let amt = from_entry + from_bets + from_sponsor + from_bounty;
         assert!(amt > 0, "E24: nothing to claim");
+        c.disburse(from_entry, from_bets, from_sponsor, from_bounty);
+        self.challenges.insert(&id, &c);
 
         claimed_map.insert(&caller, &true);
         self.has_claimed.insert(&id.to_string(), &claimed_map);
@@ -580,17 +993,173 @@ impl ChampionshipEscrow {
         Promise::new(caller).transfer(NearToken::from_yoctonear(amt))
     }
 
+    // ─── Schedule Limits ─────────────────────────────────────────────
+    /// Platform tunes the minimum compete/judge window and how far out
+    /// `judge_end` may be (i.e. the longest funds can sit in escrow), up to
+    /// MAX_HORIZON_NS.
+    pub fn set_schedule_limits(&mut self, min_phase_ns: u64, max_horizon_ns: u64) {
+        assert!(
+            env::predecessor_account_id() == self.platform,
+            "E26: not platform"
+        );
+        assert!(max_horizon_ns <= MAX_HORIZON_NS, "E4: bad schedule limits");
+        assert!(
+            min_phase_ns
+                .checked_mul(2)
+                .is_some_and(|both| max_horizon_ns > both),
+            "E4: bad schedule limits"
+        );
+        self.min_phase_ns = min_phase_ns;
+        self.max_horizon_ns = max_horizon_ns;
+        log!(
+            "Schedule limits min_phase_ns={} max_horizon_ns={}",
+            min_phase_ns,
+            max_horizon_ns
+        );
+    }
+
+    /// Platform cut of sponsor pools, in percent; applies to challenges
+    /// created afterwards (each challenge keeps the value it was made with).
+    pub fn set_sponsor_platform_pct(&mut self, pct: u8) {
+        assert!(
+            env::predecessor_account_id() == self.platform,
+            "E26: not platform"
+        );
+        assert!(pct <= MAX_SPONSOR_PLATFORM_PCT, "E37: sponsor cut too high");
+        self.sponsor_platform_pct = pct;
+        log!("Sponsor platform pct={}", pct);
+    }
+
+    // ─── Platform Transfer ───────────────────────────────────────────
+    /// Step 1: current platform nominates a successor.
+    pub fn propose_platform(&mut self, new_platform: AccountId) {
+        assert!(
+            env::predecessor_account_id() == self.platform,
+            "E26: not platform"
+        );
+        self.pending_platform = Some(new_platform.clone());
+        emit_event(
+            "platform_proposed",
+            format!(
+                "{{\"current\":\"{}\",\"pending\":\"{}\"}}",
+                self.platform, new_platform
+            ),
+        );
+    }
+
+    /// Step 2: the nominee accepts, becoming the fee recipient.
+    pub fn accept_platform(&mut self) {
+        let caller = env::predecessor_account_id();
+        assert!(
+            self.pending_platform.as_ref() == Some(&caller),
+            "E27: not pending platform"
+        );
+        let previous = std::mem::replace(&mut self.platform, caller);
+        self.pending_platform = None;
+        emit_event(
+            "platform_accepted",
+            format!(
+                "{{\"previous\":\"{}\",\"platform\":\"{}\"}}",
+                previous, self.platform
+            ),
+        );
+    }
+
     // ─── Views ───────────────────────────────────────────────────────
     pub fn get_challenge(&self, id: String) -> Option<Challenge> {
         self.challenges.get(&id)
     }
 
+    /// Header plus every agent's owner, votes and bet pool in one call.
+    /// At most MAX_AGENTS rows, since `enroll` caps the roster there.
+    pub fn get_challenge_detail(&self, id: String) -> Option<ChallengeDetail> {
+        let challenge = self.challenges.get(&id)?;
+        let ids = match self.agent_ids.get(&id) {
+            Some(v) => v,
+            None => return Some(ChallengeDetail { challenge, agents: Vec::new() }),
+        };
+        let infos = self.agents.get(&id);
+        let votes = self.vote_count.get(&id);
+        let pools = self.agent_bet_pool.get(&id);
+
+        let len = ids.len().min(MAX_AGENTS as u64);
+        let mut agents = Vec::with_capacity(len as usize);
+        for i in 0..len {
+            let agent_id = ids.get(i).unwrap();
+            let info = infos.as_ref().and_then(|m| m.get(&agent_id)).unwrap();
+            agents.push(AgentDetail {
+                vote_count: votes.as_ref().and_then(|m| m.get(&agent_id)).unwrap_or(0),
+                bet_pool: U128(pools.as_ref().and_then(|m| m.get(&agent_id)).unwrap_or(0)),
+                owner: info.owner,
+                withdrawn: info.withdrawn,
+                agent_id,
+            });
+        }
+        Some(ChallengeDetail { challenge, agents })
+    }
+
+    pub fn get_phase(&self, id: String) -> Option<Phase> {
+        self.challenges.get(&id).map(|c| c.phase(now_ns()))
+    }
+
+    pub fn get_winners(&self, id: String) -> Vec<String> {
+        self.challenges
+            .get(&id)
+            .map(|c| c.ranked_winners)
+            .unwrap_or_default()
+    }
+
+    pub fn get_platform(&self) -> AccountId {
+        self.platform.clone()
+    }
+
+    pub fn get_pending_platform(&self) -> Option<AccountId> {
+        self.pending_platform.clone()
+    }
+
+    /// (min_phase_ns, max_horizon_ns)
+    pub fn get_schedule_limits(&self) -> (u64, u64) {
+        (self.min_phase_ns, self.max_horizon_ns)
+    }
+
+    pub fn get_sponsor_pool(&self, id: String) -> U128 {
+        self.challenges
+            .get(&id)
+            .map(|c| c.sponsor_pool)
+            .unwrap_or(U128(0))
+    }
+
+    pub fn get_sponsor_platform_pct(&self) -> u8 {
+        self.sponsor_platform_pct
+    }
+
     pub fn get_agent_count(&self, id: String) -> u32 {
         self.challenges
             .get(&id)
             .map(|c| c.agent_count)
             .unwrap_or(0)
     }
+
+    pub fn is_enrolled(&self, id: String, account: AccountId) -> bool {
+        Self::nested_get(&self.has_enrolled, &id, &account).unwrap_or(false)
+    }
+
+    pub fn has_voted_view(&self, id: String, account: AccountId) -> bool {
+        Self::nested_get(&self.has_voted, &id, &account).unwrap_or(false)
+    }
+
+    pub fn has_claimed_view(&self, id: String, account: AccountId) -> bool {
+        Self::nested_get(&self.has_claimed, &id, &account).unwrap_or(false)
+    }
+
+    /// Everything `account` has bet on `id` — its refund if cancelled.
+    pub fn get_user_bet_total(&self, id: String, account: AccountId) -> U128 {
+        U128(Self::nested_get(&self.total_user_bets, &id, &account).unwrap_or(0))
+    }
+
+    pub fn get_agent_bet_pool(&self, id: String, agent_id: String) -> U128 {
+        U128(Self::nested_get(&self.agent_bet_pool, &id, &agent_id).unwrap_or(0))
+    }
 }
 
 #[cfg(test)]
@@ -607,6 +1176,36 @@ mod tests {
         "alice.testnet".parse().unwrap()
     }
 
+    fn bob() -> AccountId {
+        "bob.testnet".parse().unwrap()
+    }
+
+    fn create_default(contract: &mut ChampionshipEscrow, id: &str) {
+        contract.create(
+            id.to_string(),
+            U128(MIN_FEE),
+            2_000_000_000,
+            3_000_000_000,
+            4_000_000_000,
+            None,
+        );
+    }
+
+    fn enroll_as(
+        contract: &mut ChampionshipEscrow,
+        ctx: &mut VMContextBuilder,
+        who: AccountId,
+        id: &str,
+        agent_id: &str,
+    ) {
+        ctx.predecessor_account_id(who);
+        ctx.attached_deposit(NearToken::from_yoctonear(MIN_FEE));
+        testing_env!(ctx.build());
+        contract.enroll(id.to_string(), agent_id.to_string());
+        ctx.attached_deposit(NearToken::from_yoctonear(0));
+        testing_env!(ctx.build());
+    }
+
     fn setup() -> (ChampionshipEscrow, VMContextBuilder) {
         let mut ctx = VMContextBuilder::new();
         ctx.predecessor_account_id(alice());
@@ -625,6 +1224,7 @@ mod tests {
             2_000_000_000,
             3_000_000_000,
             4_000_000_000,
+            None,
         );
         let c = contract.get_challenge("c1".to_string()).unwrap();
         assert_eq!(c.creator, alice());
@@ -641,13 +1241,580 @@ mod tests {
             2_000_000_000,
             3_000_000_000,
             4_000_000_000,
+            None,
+        );
+        contract.create(
+            "c1".to_string(),
+            U128(MIN_FEE),
+            2_000_000_000,
+            3_000_000_000,
+            4_000_000_000,
+            None,
+        );
+    }
+
+    #[test]
+    fn test_prefix_sharing_ids_do_not_collide() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        create_default(&mut contract, "c10");
+
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "agent");
+        // Same caller and agent id in a challenge whose id extends "c1"
+        enroll_as(&mut contract, &mut ctx, bob(), "c10", "agent");
+
+        assert_eq!(contract.get_agent_count("c1".to_string()), 1);
+        assert_eq!(contract.get_agent_count("c10".to_string()), 1);
+
+        let a = near_sdk::borsh::to_vec(&StorageKey::Agents {
+            challenge_id: "c1".to_string(),
+        })
+        .unwrap();
+        let b = near_sdk::borsh::to_vec(&StorageKey::Agents {
+            challenge_id: "c10".to_string(),
+        })
+        .unwrap();
+        assert!(!b.starts_with(&a));
+    }
+
+    #[test]
+    #[should_panic(expected = "E25")]
+    fn test_oversized_id_rejected() {
+        let (mut contract, _ctx) = setup();
+        create_default(&mut contract, &"x".repeat(MAX_ID_LEN + 1));
+    }
+
+    #[test]
+    fn test_two_step_platform_transfer() {
+        let (mut contract, mut ctx) = setup();
+        ctx.predecessor_account_id(platform());
+        testing_env!(ctx.build());
+        contract.propose_platform(bob());
+        assert_eq!(contract.get_platform(), platform());
+        assert_eq!(contract.get_pending_platform(), Some(bob()));
+
+        ctx.predecessor_account_id(bob());
+        testing_env!(ctx.build());
+        contract.accept_platform();
+        assert_eq!(contract.get_platform(), bob());
+        assert_eq!(contract.get_pending_platform(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "E27")]
+    fn test_accept_platform_requires_nominee() {
+        let (mut contract, mut ctx) = setup();
+        ctx.predecessor_account_id(platform());
+        testing_env!(ctx.build());
+        contract.propose_platform(bob());
+
+        ctx.predecessor_account_id(alice());
+        testing_env!(ctx.build());
+        contract.accept_platform();
+    }
+
+    #[test]
+    fn test_finalize_bounty_refunded_on_cancel() {
+        let (mut contract, mut ctx) = setup();
+        ctx.attached_deposit(NearToken::from_yoctonear(MIN_FEE));
+        testing_env!(ctx.build());
+        create_default(&mut contract, "c1");
+        let c = contract.get_challenge("c1".to_string()).unwrap();
+        assert_eq!(c.finalize_bounty.0, MIN_FEE);
+
+        ctx.attached_deposit(NearToken::from_yoctonear(0));
+        ctx.block_timestamp(2_500_000_000);
+        testing_env!(ctx.build());
+        contract.cancel("c1".to_string());
+
+        // Creator has no entry or bets — the bounty alone is claimable
+        contract.claim("c1".to_string());
+    }
+
+    #[test]
+    fn test_podium_ranks_and_rolls_up_unfilled_tiers() {
+        let (mut contract, mut ctx) = setup();
+        contract.create(
+            "c1".to_string(),
+            U128(MIN_FEE),
+            2_000_000_000,
+            3_000_000_000,
+            4_000_000_000,
+            Some(vec![60, 30, 10]),
+        );
+        enroll_as(&mut contract, &mut ctx, alice(), "c1", "a1");
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a2");
+        enroll_as(&mut contract, &mut ctx, platform(), "c1", "a3");
+
+        ctx.block_timestamp(3_500_000_000);
+        ctx.predecessor_account_id(alice());
+        testing_env!(ctx.build());
+        contract.vote("c1".to_string(), "a2".to_string());
+        ctx.predecessor_account_id(bob());
+        testing_env!(ctx.build());
+        contract.vote("c1".to_string(), "a1".to_string());
+        ctx.predecessor_account_id(platform());
+        testing_env!(ctx.build());
+        contract.vote("c1".to_string(), "a2".to_string());
+
+        ctx.block_timestamp(4_500_000_000);
+        testing_env!(ctx.build());
+        contract.finalize("c1".to_string());
+
+        // a3 got no votes, so only two ranks are filled
+        assert_eq!(
+            contract.get_winners("c1".to_string()),
+            vec!["a2".to_string(), "a1".to_string()]
+        );
+        let c = contract.get_challenge("c1".to_string()).unwrap();
+        assert_eq!(c.podium_pct(0), 70);
+        assert_eq!(c.podium_pct(1), 30);
+    }
+
+    #[test]
+    #[should_panic(expected = "E28")]
+    fn test_podium_split_must_sum_to_100() {
+        let (mut contract, _ctx) = setup();
+        contract.create(
+            "c1".to_string(),
+            U128(MIN_FEE),
+            2_000_000_000,
+            3_000_000_000,
+            4_000_000_000,
+            Some(vec![60, 30]),
         );
+    }
+
+    #[test]
+    #[should_panic(expected = "E24")]
+    fn test_withdrawn_entry_not_refunded_again_on_cancel() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a1");
+
+        // Past enrollment, still competing
+        ctx.block_timestamp(2_500_000_000);
+        testing_env!(ctx.build());
+        contract.withdraw("c1".to_string(), "a1".to_string());
+        contract.cancel("c1".to_string());
+
+        // Withdraw already returned the entry fee — nothing left to claim
+        contract.claim("c1".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "E38: agent has bets")]
+    fn test_withdraw_blocked_once_agent_has_bets() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        let carol: AccountId = "carol.testnet".parse().unwrap();
+        enroll_as(&mut contract, &mut ctx, alice(), "c1", "a1");
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a2");
+        enroll_as(&mut contract, &mut ctx, platform(), "c1", "a3");
+
+        ctx.block_timestamp(2_500_000_000);
+        ctx.predecessor_account_id(carol);
+        ctx.attached_deposit(NearToken::from_yoctonear(MIN_FEE));
+        testing_env!(ctx.build());
+        contract.bet("c1".to_string(), "a2".to_string());
+
+        ctx.predecessor_account_id(bob());
+        ctx.attached_deposit(NearToken::from_yoctonear(0));
+        testing_env!(ctx.build());
+        contract.withdraw("c1".to_string(), "a2".to_string());
+    }
+
+    #[test]
+    fn test_finalize_skips_withdrawn_agents() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        let carol: AccountId = "carol.testnet".parse().unwrap();
+        enroll_as(&mut contract, &mut ctx, alice(), "c1", "a1");
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a2");
+        enroll_as(&mut contract, &mut ctx, platform(), "c1", "a3");
+        enroll_as(&mut contract, &mut ctx, carol, "c1", "a4");
+
+        ctx.block_timestamp(2_500_000_000);
+        ctx.predecessor_account_id(alice());
+        testing_env!(ctx.build());
+        contract.withdraw("c1".to_string(), "a1".to_string());
+
+        // Stale tallies: a1 still holds the most votes
+        let mut vc = contract.get_or_create_vote_count("c1");
+        vc.insert(&"a1".to_string(), &5);
+        vc.insert(&"a3".to_string(), &1);
+        contract.vote_count.insert(&"c1".to_string(), &vc);
+
+        ctx.block_timestamp(4_500_000_000);
+        testing_env!(ctx.build());
+        contract.finalize("c1".to_string());
+        assert_eq!(contract.get_winners("c1".to_string()), vec!["a3".to_string()]);
+    }
+
+    #[test]
+    fn test_finalize_cancels_when_withdrawals_leave_too_few() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        enroll_as(&mut contract, &mut ctx, alice(), "c1", "a1");
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a2");
+        enroll_as(&mut contract, &mut ctx, platform(), "c1", "a3");
+
+        ctx.block_timestamp(2_500_000_000);
+        ctx.predecessor_account_id(bob());
+        testing_env!(ctx.build());
+        contract.withdraw("c1".to_string(), "a2".to_string());
+
+        ctx.block_timestamp(4_500_000_000);
+        testing_env!(ctx.build());
+        contract.finalize("c1".to_string());
+        let c = contract.get_challenge("c1".to_string()).unwrap();
+        assert!(c.cancelled && !c.finalized);
+        assert_eq!(contract.get_phase("c1".to_string()), Some(Phase::Cancelled));
+    }
+
+    #[test]
+    fn test_phase_boundaries() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+
+        let cases = [
+            (2_000_000_000, Phase::Enroll),
+            (2_000_000_001, Phase::Compete),
+            (3_000_000_000, Phase::Compete),
+            (3_000_000_001, Phase::Judge),
+            (4_000_000_000, Phase::Judge),
+            (4_000_000_001, Phase::Done),
+        ];
+        for (ts, expected) in cases {
+            ctx.block_timestamp(ts);
+            testing_env!(ctx.build());
+            assert_eq!(contract.get_phase("c1".to_string()), Some(expected));
+        }
+
+        ctx.block_timestamp(2_000_000_001);
+        testing_env!(ctx.build());
+        contract.cancel("c1".to_string());
+        assert_eq!(contract.get_phase("c1".to_string()), Some(Phase::Cancelled));
+        assert_eq!(contract.get_phase("nope".to_string()), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "E4: phase too short")]
+    fn test_too_short_challenge_rejected() {
+        let (mut contract, _ctx) = setup();
         contract.create(
             "c1".to_string(),
             U128(MIN_FEE),
             2_000_000_000,
+            2_000_000_001,
+            4_000_000_000,
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "E4: schedule too far out")]
+    fn test_too_long_challenge_rejected() {
+        let (mut contract, mut ctx) = setup();
+        ctx.predecessor_account_id(platform());
+        testing_env!(ctx.build());
+        contract.set_schedule_limits(1_000_000_000, 2_500_000_000);
+        assert_eq!(contract.get_schedule_limits(), (1_000_000_000, 2_500_000_000));
+
+        // judge_end is 3s past now — half a second over the horizon
+        create_default(&mut contract, "c1");
+    }
+
+    #[test]
+    #[should_panic(expected = "E4: bad schedule limits")]
+    fn test_schedule_horizon_capped() {
+        let (mut contract, mut ctx) = setup();
+        ctx.predecessor_account_id(platform());
+        testing_env!(ctx.build());
+        contract.set_schedule_limits(1_000_000_000, MAX_HORIZON_NS);
+        assert_eq!(contract.get_schedule_limits(), (1_000_000_000, MAX_HORIZON_NS));
+
+        contract.set_schedule_limits(1_000_000_000, MAX_HORIZON_NS + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "E4: bad schedule limits")]
+    fn test_schedule_min_phase_overflow_rejected() {
+        let (mut contract, mut ctx) = setup();
+        ctx.predecessor_account_id(platform());
+        testing_env!(ctx.build());
+        // 2 * min_phase_ns would wrap to 0 and pass a plain comparison
+        contract.set_schedule_limits(1 << 63, MAX_HORIZON_NS);
+    }
+
+    #[test]
+    #[should_panic(expected = "E32")]
+    fn test_enroll_past_max_agents_rejected() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        for i in 0..=MAX_AGENTS {
+            let who: AccountId = format!("u{}.testnet", i).parse().unwrap();
+            enroll_as(&mut contract, &mut ctx, who, "c1", &format!("a{}", i));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "E23")]
+    fn test_duplicate_claims_rejected() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a1");
+
+        ctx.block_timestamp(2_500_000_000);
+        testing_env!(ctx.build());
+        contract.cancel("c1".to_string());
+
+        // Back-to-back in the same block: the second sees the first's flag
+        contract.claim("c1".to_string());
+        contract.claim("c1".to_string());
+    }
+
+    #[test]
+    fn test_cancel_refunds_match_pools() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        let carol: AccountId = "carol.testnet".parse().unwrap();
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a1");
+        enroll_as(&mut contract, &mut ctx, carol.clone(), "c1", "a2");
+
+        ctx.block_timestamp(2_500_000_000);
+        testing_env!(ctx.build());
+        contract.cancel("c1".to_string());
+
+        for who in [bob(), carol] {
+            ctx.predecessor_account_id(who);
+            testing_env!(ctx.build());
+            contract.claim("c1".to_string());
+        }
+
+        let c = contract.get_challenge("c1".to_string()).unwrap();
+        assert_eq!(c.total_disbursed.0, c.total_entry_pool.0 + c.total_bet_pool.0);
+    }
+
+    #[test]
+    fn test_rounding_never_overdraws_pools() {
+        let (mut contract, mut ctx) = setup();
+        // Odd entry fee and tiny, co-prime bets so every share truncates
+        contract.create(
+            "c1".to_string(),
+            U128(MIN_FEE + 7),
+            2_000_000_000,
             3_000_000_000,
             4_000_000_000,
+            Some(vec![34, 33, 33]),
         );
+        let fee = NearToken::from_yoctonear(MIN_FEE + 7);
+        for (who, aid) in [(alice(), "a1"), (bob(), "a2"), (platform(), "a3")] {
+            ctx.predecessor_account_id(who);
+            ctx.attached_deposit(fee);
+            testing_env!(ctx.build());
+            contract.enroll("c1".to_string(), aid.to_string());
+        }
+
+        let bettors = ["carol.testnet", "dave.testnet", "erin.testnet", "frank.testnet"];
+        ctx.block_timestamp(2_500_000_000);
+        for (who, (aid, amount)) in bettors
+            .iter()
+            .zip([("a2", 7), ("a2", 11), ("a2", 13), ("a1", 5)])
+        {
+            ctx.predecessor_account_id(who.parse().unwrap());
+            ctx.attached_deposit(NearToken::from_yoctonear(amount));
+            testing_env!(ctx.build());
+            contract.bet("c1".to_string(), aid.to_string());
+        }
+
+        ctx.attached_deposit(NearToken::from_yoctonear(0));
+        ctx.block_timestamp(3_500_000_000);
+        for (who, aid) in [(alice(), "a2"), (bob(), "a1"), (platform(), "a2")] {
+            ctx.predecessor_account_id(who);
+            testing_env!(ctx.build());
+            contract.vote("c1".to_string(), aid.to_string());
+        }
+
+        ctx.block_timestamp(4_500_000_000);
+        testing_env!(ctx.build());
+        contract.finalize("c1".to_string());
+
+        let mut claimants = vec![alice(), bob()];
+        claimants.extend(bettors[..3].iter().map(|b| b.parse().unwrap()));
+        for who in claimants {
+            ctx.predecessor_account_id(who);
+            testing_env!(ctx.build());
+            contract.claim("c1".to_string());
+        }
+
+        let c = contract.get_challenge("c1".to_string()).unwrap();
+        assert!(c.entry_disbursed.0 <= c.total_entry_pool.0);
+        assert!(c.bet_disbursed.0 <= c.total_bet_pool.0);
+        // Only truncation dust is left behind
+        assert!(c.total_entry_pool.0 - c.entry_disbursed.0 < 5);
+        assert!(c.total_bet_pool.0 - c.bet_disbursed.0 < 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "E35: payout exceeds bet pool")]
+    fn test_disburse_rejects_bet_pool_overdraw() {
+        let (mut contract, _ctx) = setup();
+        create_default(&mut contract, "c1");
+        let mut c = contract.get_challenge("c1".to_string()).unwrap();
+        c.total_bet_pool = U128(100);
+        c.disburse(0, 60, 0, 0);
+        c.disburse(0, 41, 0, 0);
+    }
+
+    #[test]
+    fn test_sponsor_pool_paid_to_winner_net_of_cut() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        enroll_as(&mut contract, &mut ctx, alice(), "c1", "a1");
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a2");
+        enroll_as(&mut contract, &mut ctx, platform(), "c1", "a3");
+
+        ctx.predecessor_account_id("carol.testnet".parse().unwrap());
+        ctx.attached_deposit(NearToken::from_yoctonear(1_000));
+        testing_env!(ctx.build());
+        contract.sponsor("c1".to_string());
+        ctx.attached_deposit(NearToken::from_yoctonear(0));
+        assert_eq!(contract.get_sponsor_pool("c1".to_string()).0, 1_000);
+
+        ctx.block_timestamp(3_500_000_000);
+        for who in [alice(), bob(), platform()] {
+            ctx.predecessor_account_id(who);
+            testing_env!(ctx.build());
+            contract.vote("c1".to_string(), "a2".to_string());
+        }
+        ctx.block_timestamp(4_500_000_000);
+        testing_env!(ctx.build());
+        contract.finalize("c1".to_string());
+
+        ctx.predecessor_account_id(bob());
+        testing_env!(ctx.build());
+        contract.claim("c1".to_string());
+
+        // 5% platform cut at finalize, the other 95% to bob's claim
+        let c = contract.get_challenge("c1".to_string()).unwrap();
+        assert_eq!(c.sponsor_platform_cut(), 50);
+        assert_eq!(c.sponsor_disbursed.0, 1_000);
+    }
+
+    #[test]
+    fn test_sponsor_pool_refunded_when_nobody_voted() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        enroll_as(&mut contract, &mut ctx, alice(), "c1", "a1");
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a2");
+        enroll_as(&mut contract, &mut ctx, platform(), "c1", "a3");
+
+        let carol: AccountId = "carol.testnet".parse().unwrap();
+        ctx.predecessor_account_id(carol.clone());
+        ctx.attached_deposit(NearToken::from_yoctonear(1_000));
+        testing_env!(ctx.build());
+        contract.sponsor("c1".to_string());
+        ctx.attached_deposit(NearToken::from_yoctonear(0));
+
+        ctx.block_timestamp(4_500_000_000);
+        testing_env!(ctx.build());
+        contract.finalize("c1".to_string());
+
+        // No votes: no first place, so no sponsor cut at finalize
+        let c = contract.get_challenge("c1".to_string()).unwrap();
+        assert!(c.ranked_winners.is_empty());
+        assert_eq!(c.sponsor_disbursed.0, 0);
+
+        ctx.predecessor_account_id(carol);
+        testing_env!(ctx.build());
+        contract.claim("c1".to_string());
+
+        let c = contract.get_challenge("c1".to_string()).unwrap();
+        assert_eq!(c.sponsor_disbursed.0, 1_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "E13")]
+    fn test_sponsor_after_judge_end_rejected() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        ctx.block_timestamp(4_500_000_000);
+        ctx.attached_deposit(NearToken::from_yoctonear(1_000));
+        testing_env!(ctx.build());
+        contract.sponsor("c1".to_string());
+    }
+
+    #[test]
+    fn test_bet_total_views() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        enroll_as(&mut contract, &mut ctx, alice(), "c1", "a1");
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a2");
+        enroll_as(&mut contract, &mut ctx, platform(), "c1", "a3");
+
+        ctx.block_timestamp(2_500_000_000);
+        ctx.predecessor_account_id(bob());
+        for (aid, amount) in [("a1", 30), ("a3", 12)] {
+            ctx.attached_deposit(NearToken::from_yoctonear(amount));
+            testing_env!(ctx.build());
+            contract.bet("c1".to_string(), aid.to_string());
+        }
+
+        assert_eq!(contract.get_user_bet_total("c1".to_string(), bob()).0, 42);
+        assert_eq!(contract.get_user_bet_total("c1".to_string(), alice()).0, 0);
+        assert_eq!(contract.get_agent_bet_pool("c1".to_string(), "a1".to_string()).0, 30);
+        assert_eq!(contract.get_agent_bet_pool("c1".to_string(), "a2".to_string()).0, 0);
+        assert_eq!(contract.get_agent_bet_pool("missing".to_string(), "a1".to_string()).0, 0);
+    }
+
+    #[test]
+    fn test_challenge_detail() {
+        let (mut contract, mut ctx) = setup();
+        assert!(contract.get_challenge_detail("c1".to_string()).is_none());
+        create_default(&mut contract, "c1");
+        assert!(contract.get_challenge_detail("c1".to_string()).unwrap().agents.is_empty());
+
+        enroll_as(&mut contract, &mut ctx, alice(), "c1", "a1");
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a2");
+        enroll_as(&mut contract, &mut ctx, platform(), "c1", "a3");
+
+        ctx.block_timestamp(2_500_000_000);
+        ctx.predecessor_account_id(bob());
+        ctx.attached_deposit(NearToken::from_yoctonear(25));
+        testing_env!(ctx.build());
+        contract.bet("c1".to_string(), "a2".to_string());
+
+        let detail = contract.get_challenge_detail("c1".to_string()).unwrap();
+        assert_eq!(detail.challenge.agent_count, 3);
+        let rows: Vec<(&str, u128)> = detail
+            .agents
+            .iter()
+            .map(|a| (a.agent_id.as_str(), a.bet_pool.0))
+            .collect();
+        assert_eq!(rows, vec![("a1", 0), ("a2", 25), ("a3", 0)]);
+        assert_eq!(detail.agents[1].owner, bob());
+        assert!(detail.agents.iter().all(|a| a.vote_count == 0 && !a.withdrawn));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_account_status_views() {
+        let (mut contract, mut ctx) = setup();
+        create_default(&mut contract, "c1");
+        assert!(!contract.is_enrolled("c1".to_string(), bob()));
+        enroll_as(&mut contract, &mut ctx, bob(), "c1", "a1");
+        assert!(contract.is_enrolled("c1".to_string(), bob()));
+        assert!(!contract.is_enrolled("c1".to_string(), alice()));
+        assert!(!contract.is_enrolled("missing".to_string(), bob()));
+        assert!(!contract.has_voted_view("c1".to_string(), bob()));
+
+        ctx.block_timestamp(2_500_000_000);
+        testing_env!(ctx.build());
+        contract.cancel("c1".to_string());
+        assert!(!contract.has_claimed_view("c1".to_string(), bob()));
+        ctx.predecessor_account_id(bob());
+        testing_env!(ctx.build());
+        contract.claim("c1".to_string());
+        assert!(contract.has_claimed_view("c1".to_string(), bob()));
+    }
+}
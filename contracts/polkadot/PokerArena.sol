@@ -3,7 +3,8 @@ pragma solidity ^0.8.0;
 
 /// @title Poker Arena — Unified poker + betting contract
 /// @notice Global VPS dealer manages all tables. Anyone creates tables.
-///         Bots join and play. Humans bet on bots. Platform earns 5%.
+///         Bots join and play. Humans bet on bots. Platform earns a fee
+///         (5% by default, set per table at creation).
 interface PokerArena {
 
     // ========================================================================
@@ -11,54 +12,212 @@ interface PokerArena {
     // ========================================================================
     event TableCreated(uint32 indexed tableId);
     event AgentJoined(uint32 indexed tableId, uint8 seat);
+    /// @param movedFrom Seat whose agent now sits at `seat` (== seat if the
+    ///        leaver held the last seat)
+    event AgentLeft(uint32 indexed tableId, uint8 seat, uint8 movedFrom);
     event BetPlaced(uint32 indexed tableId, uint8 agentSeat);
-    event HandResolved(uint32 indexed tableId, uint32 handNumber, uint8 winningSeat);
+    /// @param resolution 0=fold-win, 1=dealer showdown, 2=split pot, 3=corrected
+    /// @param netWon Pot minus the winning seat's own chips in it this hand
+    /// @param amount Gross chips moved to the seat (its share on a split)
+    /// @param chipsAfter The seat's stack once the pot is credited
+    ///        (amount and chipsAfter are appended to the data; topic unchanged)
+    event HandResolved(uint32 indexed tableId, uint32 handNumber, uint8 winningSeat, uint8 resolution, uint256 netWon, uint256 amount, uint256 chipsAfter);
     event SessionEnded(uint32 indexed tableId);
     event AgentKicked(uint32 indexed tableId, uint8 seat, address agent);
+    event Heartbeat(uint32 indexed tableId, uint64 timestamp);
+    event BetCancelled(uint32 indexed tableId, uint8 agentSeat);
+    /// @notice Antes taken at the start of a hand (only when the table has one)
+    event AntesCollected(uint32 indexed tableId, uint32 handNumber, uint256 total);
+    /// @notice A busted (or, if allowed, kicked) agent bought back in
+    event Rebuy(uint32 indexed tableId, uint8 seat, uint256 amount);
+    /// @notice Payouts actually sent (or credited to the owed balance if the
+    ///         push failed). claimAll emits ChipsClaimed and/or PrizeClaimed;
+    ///         claimAllBets one BetWinningsClaimed for its combined payout.
+    event ChipsClaimed(uint32 indexed tableId, address claimant, uint256 amount);
+    event PrizeClaimed(uint32 indexed tableId, address claimant, uint256 amount);
+    event BetWinningsClaimed(uint32 indexed tableId, address claimant, uint256 amount);
+    /// @notice Platform paused (true) or resumed (false) the contract
+    event PausedSet(bool paused);
+    /// @notice Accrued platform fees paid out by sweepFees
+    event FeesSwept(address indexed platform, uint256 amount);
+    /// @param pool 0=prize pool, 1=bet pool. Emitted once per pool, on the first claim.
+    ///        The platform fee accrues on-chain (see sweepFees); the creator fee is sent.
+    event FeePaid(uint32 indexed tableId, uint8 pool, uint256 platformFee, uint256 creatorFee);
 
     // ========================================================================
     // TABLE MANAGEMENT
     // ========================================================================
 
     /// @notice Create a table. msg.value = prize pool (locked until session end).
-    /// @param buyIn   Exact DOT each agent must send to join
+    /// @param buyIn   Exact DOT each agent must send to join; must lie in
+    ///        getBuyInBounds()
     /// @param maxAgents 2–8
     /// @param sessionLength Number of hands in the session
     /// @param betDeadline  Unix timestamp — human bets rejected after this
+    /// @param forfeitPolicy Kicked agent's chips: 0=to prize pool,
+    ///        1=to remaining agents pro rata by chips, 2=burned (unclaimable)
+    /// @param allowRebuyAfterKick true = a kicked agent may rebuy, which
+    ///        clears the kick and puts the seat back in the rotation
+    /// @param minHands Hands that must be played before kicks alone can end
+    ///        the session (0 = no floor, must be <= sessionLength)
+    /// @param creatorFeeBps Creator's cut of the prize and bet pools, taken
+    ///        with the platform fee at claim time; platform fee + this may
+    ///        not exceed 10%
+    /// @param sessionSecs Time budget from the first deal (0 = 7 days, the
+    ///        max). Must allow sessionLength hands at 10s each. Once spent no
+    ///        new hand is dealt and the next resolve ends the session.
+    /// @param tiePolicy Prize split when chip leaders tie: 0=even split
+    ///        (remainder to the lowest tied seat), 1=lowest tied seat takes all
+    /// @param smallBlind Forced bet posted left of the button each hand
+    /// @param bigBlind   Forced bet posted left of the small blind; opens the
+    ///        betting at this amount (0/0 = no blinds, smallBlind <= bigBlind <= buyIn)
+    /// @param ante Dead money every dealt-in agent posts each hand before the
+    ///        blinds (0 = none, <= buyIn); a short stack posts what it has
+    /// @param token ERC-20 the table is denominated in (address(0) = native).
+    ///        Prize pool, buy-ins and bets are then pulled with transferFrom
+    ///        (approve this contract first) and every payout is a token transfer.
+    /// @param tokenPrizePool Prize pool pulled from the caller on a token
+    ///        table (msg.value must be 0); must be 0 on a native table
+    /// @param inactivitySecs Dealer silence after which anyone may cancel the
+    ///        Playing table (0 = the global window, else 600 to 7 days)
+    /// @param kickThreshold Consecutive timeouts before an agent is kicked
+    ///        (0 = the global threshold, 3 unless the platform overrides it)
     /// @return tableId
     function createTable(
         uint256 buyIn,
         uint8   maxAgents,
         uint32  sessionLength,
-        uint64  betDeadline
+        uint64  betDeadline,
+        uint8   forfeitPolicy,
+        bool    allowRebuyAfterKick,
+        uint32  minHands,
+        uint16  creatorFeeBps,
+        uint64  sessionSecs,
+        uint8   tiePolicy,
+        uint256 smallBlind,
+        uint256 bigBlind,
+        uint256 ante,
+        address token,
+        uint256 tokenPrizePool,
+        uint64  inactivitySecs,
+        uint8   kickThreshold
     ) external payable returns (uint32 tableId);
 
-    /// @notice Agent joins table. Must send exact buyIn as msg.value.
+    /// @notice Agent joins table. Must send exact buyIn as msg.value, or on a
+    ///         token table have approved buyIn of the token (msg.value = 0).
+    ///         Only before betDeadline: the roster bettors wagered on is
+    ///         frozen when betting closes.
     /// @return seat Seat number assigned (0-indexed)
     function joinTable(uint32 tableId) external payable returns (uint8 seat);
 
+    /// @notice Agent leaves an Open table before the first deal and gets its
+    ///         buy-in back. Bets on its seat are refunded; the last seat's
+    ///         agent (and the bets backing it) moves into the vacated seat.
+    function leaveTable(uint32 tableId) external;
+
+    /// @notice Agent with 0 chips buys back in for buyIn (msg.value, or the
+    ///         token on a token table) between hands of a Playing session
+    ///         (pot empty). A kicked agent may only if allowRebuyAfterKick;
+    ///         that clears the kick. Rebuys become chips — the prize pool is
+    ///         unchanged.
+    function rebuy(uint32 tableId) external payable;
+
     /// @notice Human places bet on an agent. msg.value = bet amount.
     ///         Only accepted while state=Open and before betDeadline.
-    function placeBet(uint32 tableId, uint8 agentSeat) external payable;
+    /// @return betIndex Index of the new bet record (used by cancelBet)
+    function placeBet(uint32 tableId, uint8 agentSeat) external payable returns (uint8 betIndex);
+
+    /// @notice Bettor cancels one of their bets and is refunded in full.
+    ///         Only while state=Open and before betDeadline.
+    function cancelBet(uint32 tableId, uint8 betIndex) external;
+
+    /// @notice placeBet for token tables: `amount` of the table's token is
+    ///         pulled with transferFrom. placeBet reverts on token tables.
+    function placeBetToken(uint32 tableId, uint8 agentSeat, uint256 amount) external returns (uint8 betIndex);
+
+    /// @notice Bettor cancels every live bet they hold on the table and is
+    ///         refunded in full. Only while state=Open and before betDeadline.
+    function withdrawBet(uint32 tableId) external;
 
     // ========================================================================
     // DEALER ACTIONS (global VPS only)
     // ========================================================================
 
-    /// @notice Start a hand. Resets per-hand state. Closes betting on first deal.
+    /// @notice Start a hand. Resets per-hand state, moves the button one seat
+    ///         left and posts the blinds. Closes betting on first deal.
     /// @param deckHash Commitment to the shuffled deck (revealed at showdown off-chain)
-    function deal(uint32 tableId, bytes32 deckHash) external;
+    /// @return firstToAct  Seat whose turn it is (left of the big blind;
+    ///         the button/small blind when heads-up)
+    /// @return activeCount Agents dealt into this hand
+    /// @return handNumber  The hand just started (1-indexed)
+    function deal(uint32 tableId, bytes32 deckHash) external returns (
+        uint8  firstToAct,
+        uint8  activeCount,
+        uint32 handNumber
+    );
 
     /// @notice Submit a player action.
     ///         If caller = dealer → timeout fold → missed turns counter ticked.
-    ///         3 missed turns → agent kicked, chips forfeit to prize pool.
+    ///         kickThreshold missed turns (3 by default) → agent kicked,
+    ///         chips forfeited per forfeitPolicy.
     /// @param action 0=fold, 1=check, 2=call, 3=raise
-    /// @param amount Chips to raise by (only for action=3)
+    /// @param amount Chips to raise by (only for action=3). At least the last
+    ///        full raise (big blind first) unless the raise puts the agent all-in
     function action(uint32 tableId, uint8 action, uint256 amount) external;
 
+    /// @notice Anyone may fold the agent to act once its turn deadline
+    ///         (getTurnDeadline) has passed. Counts as a missed turn exactly
+    ///         like a dealer timeout fold. Reverts if the table has no turn
+    ///         clock (setTurnTimeout).
+    function forceFold(uint32 tableId) external;
+
+    /// @notice Open the next street once getRoundComplete is true: every
+    ///         agent may act again, the min raise resets to the big blind and
+    ///         action starts left of the button.
+    function advanceRound(uint32 tableId) external;
+
+    /// @notice Seconds each agent gets to act before anyone can forceFold it
+    ///         (0 = off, the default: only the dealer submits timeouts).
+    ///         Applies from the next turn. Open or Playing tables.
+    function setTurnTimeout(uint32 tableId, uint64 turnSecs) external;
+
     /// @notice Award pot to hand winner. Checks session-end condition.
+    ///         Reverts unless getRoundComplete — every agent still in with
+    ///         chips behind has acted and matched the bet. Same for
+    ///         resolveHandMulti; batchResolve skips/reverts such tables.
     function resolveHand(uint32 tableId, uint8 winningSeat) external;
 
+    /// @notice Split the pot between tied winners. Bit i of winnerMask =
+    ///         seat i (each must be in the hand). Each gets pot / n, the
+    ///         lowest winning seat also gets the remainder. Emits
+    ///         HandResolved(resolution=2) per winner; not disputable.
+    function resolveHandMulti(uint32 tableId, uint8 winnerMask) external;
+
+    /// @notice Deal the next hand on up to 16 tables in one transaction.
+    /// @param skipFailures true = leave ineligible tables untouched,
+    ///        false = revert the whole batch if any table can't be dealt
+    /// @return dealtMask Bit i set if tableIds[i] was dealt
+    function batchDeal(uint32[] calldata tableIds, bool skipFailures)
+        external returns (uint32 dealtMask);
+
+    /// @notice resolveHand for up to 16 tables; winners[i] wins tableIds[i].
+    ///         Emits HandResolved per table. Same skipFailures semantics.
+    /// @return resolvedMask Bit i set if tableIds[i] was resolved
+    function batchResolve(uint32[] calldata tableIds, uint8[] calldata winners, bool skipFailures)
+        external returns (uint32 resolvedMask);
+
+    /// @notice Correct a mis-resolved hand: moves the last resolveHand pot
+    ///         from the seat it went to onto `correctWinner`. Only before the
+    ///         next deal/action, within 120s, and (if the session ended)
+    ///         before any prize/bet payout or chip claim by either seat.
+    ///         Emits HandResolved with resolution=3.
+    function disputeResolve(uint32 tableId, uint8 correctWinner) external;
+
+    /// @notice Dealer liveness ping for a Playing table. Refreshes lastAction
+    ///         without changing game state. Rejected once the table's
+    ///         sessionSecs budget is spent so heartbeats can't hold escrow forever.
+    function heartbeat(uint32 tableId) external;
+
     /// @notice Dealer manually ends the session (or cancels if still Open).
     function endSession(uint32 tableId) external;
 
@@ -68,7 +227,9 @@ interface PokerArena {
 
     /// @notice Cancel a table if dealer vanished. Strictly guarded:
     ///         (A) state=Open AND now > betDeadline AND no hands dealt, OR
-    ///         (B) state=Playing AND now - lastAction > 3600 seconds.
+    ///         (B) state=Playing AND now - lastAction > 3600 seconds, OR
+    ///         (C) state=Open AND no agents joined AND caller = creator
+    ///             (any time — creator then calls refund for the prize pool).
     ///         Griefing impossible — active games update lastAction every tx.
     function cancel(uint32 tableId) external;
 
@@ -79,6 +240,32 @@ interface PokerArena {
     /// @notice Update the global dealer address. Only platform (deployer) can call.
     function updateDealer(address newDealer) external;
 
+    /// @notice Override the inactivity window (seconds) and missed-turns kick
+    ///         threshold for all tables (a table created with its own
+    ///         inactivitySecs keeps it). Pass 0 to restore the default
+    ///         (3600s / 3 misses); a nonzero inactivitySecs must be 600 to
+    ///         7 days. Only platform can call.
+    function setGlobalThresholds(uint64 inactivitySecs, uint8 kickThreshold) external;
+
+    /// @notice Set the platform fee (bps, max 1000) for tables created from
+    ///         now on; existing tables keep the rate they were created with.
+    ///         500 at deploy. Only platform can call.
+    function updateFee(uint16 feeBps) external;
+
+    /// @notice Set the buy-in band createTable enforces for new tables.
+    ///         0 < minBuyIn <= maxBuyIn <= 2^112 - 1 (the deploy default max,
+    ///         which keeps stacks and pools summable). Only platform can call.
+    function setBuyInBounds(uint256 minBuyIn, uint256 maxBuyIn) external;
+
+    /// @notice Address currently allowed to deal, act on timeouts and resolve.
+    function getDealer() external view returns (address);
+
+    /// @notice Platform admin and platform-fee recipient.
+    function getPlatform() external view returns (address);
+
+    /// @notice Effective buy-in band, for pre-validating createTable.
+    function getBuyInBounds() external view returns (uint256 minBuyIn, uint256 maxBuyIn);
+
     // ========================================================================
     // PAYOUTS
     // ========================================================================
@@ -87,23 +274,93 @@ interface PokerArena {
     ///         Valid only after session ends.
     function claimChips(uint32 tableId) external;
 
-    /// @notice Chip leader(s) claim prize pool. 5% platform fee deducted once.
-    ///         If tied, each tied agent gets equal share of 95%.
+    /// @notice Chip leader(s) claim prize pool. Platform fee plus the
+    ///         table's creator fee deducted once; tied agents split the rest
+    ///         per the table's tiePolicy.
     function claimPrize(uint32 tableId) external;
 
+    /// @notice claimChips + claimPrize in one transaction: unclaimed chips
+    ///         plus the prize share if the caller is a chip leader. Parts
+    ///         already claimed are skipped; reverts if nothing is owed.
+    function claimAll(uint32 tableId) external;
+
     /// @notice Bettors who backed the chip leader claim winnings.
-    ///         5% platform fee deducted once from total bet pool.
-    ///         Payout proportional to bet size vs total bets on that agent.
+    ///         Platform fee plus the creator fee deducted once from the bet pool.
+    ///         Payout proportional to bet size vs total bets on the winning
+    ///         agent(s) — with tied leaders, the bets on all tied seats combined.
     function claimBetWinnings(uint32 tableId) external;
 
+    /// @notice claimBetWinnings for all of the caller's winning bets at once,
+    ///         paid as one transfer. Reverts if none are unclaimed.
+    function claimAllBets(uint32 tableId) external;
+
+    /// @notice Pull payouts whose push transfer failed (e.g. the recipient
+    ///         contract rejected value). Every claim, refund and fee transfer
+    ///         credits the recipient here instead of losing the funds.
+    function withdrawOwed() external;
+
+    /// @notice Balance withdrawOwed would pay `account`.
+    function getOwed(address account) external view returns (uint256);
+
+    /// @notice withdrawOwed for a token table: pulls the caller's token
+    ///         payouts from `tableId` whose transfer failed (e.g. a
+    ///         blacklisted recipient), in the table's token.
+    function withdrawTokenOwed(uint32 tableId) external;
+
+    /// @notice Balance withdrawTokenOwed(tableId) would pay `account`.
+    function getTokenOwed(uint32 tableId, address account) external view returns (uint256);
+
+    /// @notice Platform only. While paused, createTable, joinTable, placeBet
+    ///         and placeBetToken revert; claims, refunds, cancel and every
+    ///         other exit keep working.
+    function setPaused(bool paused) external;
+
+    function getPaused() external view returns (bool);
+
+    /// @notice Platform only. Transfers every platform fee accrued across
+    ///         all tables since the last sweep.
+    function sweepFees() external;
+
+    /// @notice sweepFees for platform fees accrued in `token` by token tables.
+    function sweepTokenFees(address token) external;
+
+    /// @notice Native platform fees accrued and not yet swept.
+    function getPlatformAccrued() external view returns (uint256);
+
     /// @notice Full refunds for state=Cancelled tables.
-    ///         Creator gets prize pool. Agents get buy-in. Bettors get bet amount.
+    ///         Creator gets prize pool. Agents get their chips plus, if
+    ///         cancelled mid-hand, what they put into the unawarded pot
+    ///         (= buy-in before the first deal). Bettors get bet amount.
     function refund(uint32 tableId) external;
 
     // ========================================================================
     // VIEW FUNCTIONS
     // ========================================================================
 
+    struct TableSummary {
+        uint32  tableId;
+        uint8   state;
+        uint8   agentCount;
+        uint256 buyIn;
+        uint256 prizePool;
+    }
+
+    /// @notice Lobby page of tables [start, start+count). Clamped to existing
+    ///         tables and to at most 16 rows per call.
+    function getTables(uint32 start, uint8 count) external view returns (TableSummary[] memory);
+
+    struct TableMatch {
+        uint32  tableId;
+        uint8   agentCount;
+        uint256 buyIn;
+    }
+
+    /// @notice Tables from `start` on whose state matches, at most `limit`
+    ///         (<= 16) rows. Examines at most 256 ids per call; resume from
+    ///         `next` until it reaches the table count.
+    function getTablesByState(uint8 state, uint32 start, uint8 limit)
+        external view returns (TableMatch[] memory rows, uint32 next);
+
     function getTableInfo(uint32 tableId) external view returns (
         address creator,
         uint256 prizePool,
@@ -113,15 +370,63 @@ interface PokerArena {
         uint32  sessionLength,
         uint32  currentHand,
         uint64  betDeadline,
-        uint8   state  // 0=Open, 1=Playing, 2=Ended, 3=Cancelled
+        uint8   state, // 0=Open, 1=Playing, 2=Ended, 3=Cancelled
+        uint16  creatorFeeBps,
+        uint8   tiePolicy, // 0=even split, 1=earliest seat
+        uint8   kickThreshold, // effective: the table's own, else the global
+        uint64  inactivitySecs // effective, like kickThreshold
     );
 
+    /// @notice Number of tables created; ids are 0..count-1. createTable
+    ///         reverts once this reaches type(uint32).max.
+    function getTableCount() external view returns (uint32);
+
+    /// @notice prizePool + total bet pool + all agent stacks + pot: what the
+    ///         contract holds for the table until claims/refunds start (then
+    ///         see getTableLiabilities). Monitoring invariant vs. balance.
+    function getTableTVL(uint32 tableId) external view returns (uint256);
+
+    /// @notice Total `account` could claim from the table right now: on an
+    ///         Ended table unclaimed chips + prize share + winning bets, on a
+    ///         Cancelled one everything refund() would return (over as many
+    ///         calls as it takes). 0 for Open/Playing. Excludes getOwed and
+    ///         getTokenOwed.
+    function getClaimable(uint32 tableId, address account) external view returns (uint256);
+
+    /// @notice When the agent to act can be force-folded (0 = no clock
+    ///         running).
+    function getTurnDeadline(uint32 tableId) external view returns (uint64);
+
+    /// @notice Seat `agent` holds on the table, or 255 if it isn't seated.
+    function getSeatOf(uint32 tableId, address agent) external view returns (uint8 seat);
+
+    /// @notice ERC-20 the table is denominated in; address(0) = native.
+    function getTableToken(uint32 tableId) external view returns (address);
+
+    /// @notice One seat's state. rebought = total buy-ins added by rebuy
+    ///         this session, on top of the original buy-in.
     function getAgentInfo(uint32 tableId, uint8 seat) external view returns (
         address agent,
         uint256 chips,
         bool    folded,
         bool    kicked,
-        uint8   missedTurns
+        uint8   missedTurns,
+        uint256 rebought
+    );
+
+    struct AgentRow {
+        address agent;
+        uint256 chips;
+        bool    folded;
+        bool    kicked;
+        uint8   missedTurns;
+    }
+
+    /// @notice Every seat's agent, chips, folded, kicked and missedTurns in
+    ///         one call. Rows past agentCount are zero.
+    function getAllAgents(uint32 tableId) external view returns (
+        uint8         agentCount,
+        AgentRow[8] memory agents
     );
 
     function getBetInfo(uint32 tableId, address bettor) external view returns (
@@ -130,7 +435,89 @@ interface PokerArena {
         bool    claimed
     );
 
+    /// @notice Bet record by index; page from 0 until it reverts (past the
+    ///         last bet) to rebuild the full ledger — getBetInfo returns
+    ///         only an address's first bet.
+    function getBetByIndex(uint32 tableId, uint8 betIndex) external view returns (
+        address bettor,
+        uint8   agentSeat,
+        uint256 amount,
+        bool    claimed,
+        bool    cancelled
+    );
+
+    /// @notice Live hand state. activeCount = in this hand (not folded/kicked),
+    ///         aliveCount = in the session (not kicked). button = dealer-button
+    ///         seat, moved one non-kicked seat left on every deal.
+    function getGameState(uint32 tableId) external view returns (
+        uint8   state,
+        uint32  currentHand,
+        uint8   currentTurn,
+        uint256 currentBet,
+        uint256 pot,
+        uint8   activeCount,
+        uint8   aliveCount,
+        uint8   button
+    );
+
+    /// @notice Per-seat flags for the current hand, indexed by seat (unused
+    ///         seats = 0). Bits: 1=dealt in, 2=folded, 4=all-in (no chips
+    ///         behind), 8=acted this hand, 16=kicked.
+    function getHandStatus(uint32 tableId) external view returns (uint8[8] memory status);
+
+    /// @notice True once every agent still in the hand with chips behind has
+    ///         acted since the last full raise and matched the current bet —
+    ///         safe to advanceRound or resolve.
+    function getRoundComplete(uint32 tableId) external view returns (bool);
+
+    /// @notice Whether msg.sender is the agent to act, and what a call would
+    ///         cost it (0 when not its turn).
+    function isMyTurn(uint32 tableId) external view returns (bool myTurn, uint256 toCall);
+
     function getAgentBetTotal(uint32 tableId, uint8 seat) external view returns (uint256);
+
+    /// @notice Payout if `amount` were bet on `seat` now and that seat won
+    ///         outright. Counts `amount` in both the seat total and the pool,
+    ///         net of platform and creator fees — same math as claimBetWinnings.
+    function quoteBet(uint32 tableId, uint8 seat, uint256 amount) external view returns (uint256 payout);
     function getTotalBetPool(uint32 tableId)              external view returns (uint256);
+    /// @notice Sum of the bettor's live (non-cancelled) bets across all seats.
+    function getBettorTotal(uint32 tableId, address bettor) external view returns (uint256);
     function getCurrentPot(uint32 tableId)                external view returns (uint256);
+
+    /// @notice Final standings; reverts unless state=Ended. chips is indexed
+    ///         by seat (unused seats = 0), bit i of leaderMask = seat i is
+    ///         paid by claimPrize under the tie policy, payoutPerWinner = each
+    ///         such seat's amount after platform and creator fees (on an even
+    ///         split the lowest seat also gets the division remainder).
+    function getFinalResults(uint32 tableId) external view returns (
+        uint256[8] memory chips,
+        uint8   leaderMask,
+        uint8   leaderCount,
+        uint256 prizePool,
+        uint256 payoutPerWinner
+    );
+
+    /// @notice What the table still owes across every claim path, for
+    ///         comparing against the contract balance. prize and bets include
+    ///         fees not yet paid; chips covers stacks + pot (or unrefunded
+    ///         buy-ins once Cancelled). Burned forfeits are not owed.
+    function getTableLiabilities(uint32 tableId) external view returns (
+        uint256 total,
+        uint256 prize,
+        uint256 chips,
+        uint256 bets
+    );
+
+    /// @notice Platform-fee audit trail for a table. Fee amounts are what
+    ///         is (or will be) taken from each pool at the current fee rate.
+    ///         unsweptFee is the part of the table's taken fees still accrued
+    ///         (0 once a sweepFees / sweepTokenFees has collected it).
+    function getFeeStatus(uint32 tableId) external view returns (
+        bool    prizeFeePaid,
+        bool    betFeePaid,
+        uint256 prizeFee,
+        uint256 betFee,
+        uint256 unsweptFee
+    );
 }
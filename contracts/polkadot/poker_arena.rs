@@ -6,12 +6,12 @@ use pallet_revive_uapi::{HostFn, HostFnImpl as api, ReturnFlags, StorageFlags};
 
 // ============================================================================
 // POKER ARENA — Final Contract
-// Global dealer (VPS), prize pool per table, 5% platform fee,
+// Global dealer (VPS), prize pool per table, platform fee (5% default),
 // kick-on-3-misses, permissionless cancel with inactivity guard.
 // ============================================================================
 
 // ---- Function Selectors (keccak256 of Solidity ABI signatures) -------------
-const CREATE_TABLE_SEL: [u8; 4] = [0xd6, 0x62, 0xaa, 0x80];
+const CREATE_TABLE_SEL: [u8; 4] = [0x06, 0x30, 0x4c, 0x8f];
 const JOIN_TABLE_SEL: [u8; 4] = [0x31, 0x27, 0x25, 0x27];
 const PLACE_BET_SEL: [u8; 4] = [0x93, 0x03, 0xfd, 0xed];
 const DEAL_SEL: [u8; 4] = [0xc0, 0x87, 0xcb, 0xbb];
@@ -30,6 +30,55 @@ const GET_BET_INFO_SEL: [u8; 4] = [0x36, 0xff, 0x33, 0x2a];
 const GET_AGENT_BET_TOTAL_SEL: [u8; 4] = [0x6b, 0x4c, 0xd3, 0x5b];
 const GET_TOTAL_BET_POOL_SEL: [u8; 4] = [0xbc, 0x32, 0x1b, 0x77];
 const GET_CURRENT_POT_SEL: [u8; 4] = [0xa5, 0x45, 0x39, 0xb0];
+const SET_GLOBAL_THRESHOLDS_SEL: [u8; 4] = [0xd2, 0x9b, 0x8f, 0x00];
+const HEARTBEAT_SEL: [u8; 4] = [0xfe, 0x9c, 0x95, 0x7c];
+const GET_FEE_STATUS_SEL: [u8; 4] = [0x7f, 0x54, 0x3e, 0x67];
+const CANCEL_BET_SEL: [u8; 4] = [0x35, 0x32, 0x5e, 0x92];
+const BATCH_DEAL_SEL: [u8; 4] = [0xfc, 0x6b, 0x63, 0x69];
+const BATCH_RESOLVE_SEL: [u8; 4] = [0xfc, 0xd5, 0x1c, 0x4e];
+const GET_BETTOR_TOTAL_SEL: [u8; 4] = [0x50, 0xec, 0x1c, 0x9b];
+const GET_BET_BY_INDEX_SEL: [u8; 4] = [0xe1, 0xa9, 0xff, 0x7c];
+const GET_TABLES_SEL: [u8; 4] = [0x47, 0x57, 0x9c, 0x8c];
+const QUOTE_BET_SEL: [u8; 4] = [0x27, 0x3e, 0xe9, 0xb3];
+const GET_GAME_STATE_SEL: [u8; 4] = [0x11, 0xbb, 0x59, 0xb3];
+const DISPUTE_RESOLVE_SEL: [u8; 4] = [0x98, 0x96, 0xcc, 0x7d];
+const GET_HAND_STATUS_SEL: [u8; 4] = [0x76, 0xc9, 0x22, 0x0d];
+const IS_MY_TURN_SEL: [u8; 4] = [0x9d, 0x85, 0x0a, 0xa2];
+const GET_TABLE_LIABILITIES_SEL: [u8; 4] = [0x38, 0x21, 0xc7, 0x5e];
+const GET_FINAL_RESULTS_SEL: [u8; 4] = [0xe1, 0x6d, 0x60, 0x7f];
+const CLAIM_ALL_BETS_SEL: [u8; 4] = [0x91, 0x38, 0x8b, 0x72];
+const RESOLVE_HAND_MULTI_SEL: [u8; 4] = [0x6b, 0xec, 0x42, 0xea];
+const LEAVE_TABLE_SEL: [u8; 4] = [0x4c, 0x05, 0xa4, 0x91];
+const GET_TABLES_BY_STATE_SEL: [u8; 4] = [0x51, 0x33, 0xf8, 0x73];
+const GET_ALL_AGENTS_SEL: [u8; 4] = [0xaf, 0xd1, 0x66, 0x94];
+const UPDATE_FEE_SEL: [u8; 4] = [0x2c, 0x6c, 0xda, 0x93];
+const WITHDRAW_OWED_SEL: [u8; 4] = [0x39, 0xa7, 0x2c, 0x5c];
+const GET_OWED_SEL: [u8; 4] = [0x70, 0xc0, 0xb6, 0x47];
+const GET_ROUND_COMPLETE_SEL: [u8; 4] = [0x92, 0xf3, 0x3b, 0x9b];
+const ADVANCE_ROUND_SEL: [u8; 4] = [0x2a, 0xa1, 0xbd, 0xc1];
+const WITHDRAW_BET_SEL: [u8; 4] = [0x6f, 0x63, 0x7b, 0x88];
+const CLAIM_ALL_SEL: [u8; 4] = [0x72, 0xda, 0x5b, 0xcb];
+const SWEEP_FEES_SEL: [u8; 4] = [0xd1, 0x13, 0xb9, 0x5c];
+const GET_PLATFORM_ACCRUED_SEL: [u8; 4] = [0x8c, 0x29, 0xc3, 0xae];
+const PLACE_BET_TOKEN_SEL: [u8; 4] = [0x17, 0x0c, 0x61, 0x67];
+const SWEEP_TOKEN_FEES_SEL: [u8; 4] = [0x7c, 0xad, 0x46, 0x65];
+const GET_TABLE_TOKEN_SEL: [u8; 4] = [0x0d, 0xa6, 0x03, 0x2f];
+const SET_PAUSED_SEL: [u8; 4] = [0x16, 0xc3, 0x8b, 0x3c];
+const GET_PAUSED_SEL: [u8; 4] = [0x68, 0x05, 0xb8, 0x4b];
+const SET_BUY_IN_BOUNDS_SEL: [u8; 4] = [0x0b, 0xe1, 0x3c, 0x7e];
+const GET_BUY_IN_BOUNDS_SEL: [u8; 4] = [0x14, 0x47, 0xd3, 0x6d];
+const GET_SEAT_OF_SEL: [u8; 4] = [0xa8, 0x6f, 0x86, 0x0c];
+const SET_TURN_TIMEOUT_SEL: [u8; 4] = [0xb2, 0x14, 0x0a, 0x4a];
+const FORCE_FOLD_SEL: [u8; 4] = [0x0f, 0xc5, 0xd5, 0xea];
+const GET_TURN_DEADLINE_SEL: [u8; 4] = [0x8a, 0x90, 0xad, 0x5d];
+const GET_DEALER_SEL: [u8; 4] = [0x9c, 0xf6, 0xd1, 0xaf];
+const GET_PLATFORM_SEL: [u8; 4] = [0x2f, 0xc1, 0xf1, 0x90];
+const REBUY_SEL: [u8; 4] = [0x2d, 0x89, 0x6c, 0x95];
+const GET_CLAIMABLE_SEL: [u8; 4] = [0x6c, 0x1d, 0x05, 0x82];
+const GET_TABLE_TVL_SEL: [u8; 4] = [0xf6, 0xc6, 0x12, 0x62];
+const GET_TABLE_COUNT_SEL: [u8; 4] = [0x7f, 0x9b, 0xb3, 0x14];
+const WITHDRAW_TOKEN_OWED_SEL: [u8; 4] = [0xc9, 0xfc, 0x2a, 0x91];
+const GET_TOKEN_OWED_SEL: [u8; 4] = [0xda, 0x8d, 0x85, 0x59];
 
 // ---- Event Topics -----------------------------------------------------------
 const TABLE_CREATED_TOPIC: [u8; 32] = [0x3e,0xb7,0x00,0xd7,0x44,0x54,0xd1,0x6e,0xe2,0xb9,0xcc,0x59,0x25,0xec,0xa3,0xba,0x71,0x25,0x53,0xc5,0x63,0x6d,0x65,0x99,0xea,0x95,0x07,0xea,0xfe,0xa9,0x19,0xbb];
@@ -38,6 +87,17 @@ const BET_PLACED_TOPIC: [u8; 32] = [0xde,0x07,0xa5,0x2b,0xf7,0x80,0xdc,0x7b,0x08
 const HAND_RESOLVED_TOPIC: [u8; 32] = [0x20,0x71,0x64,0x66,0x0a,0x45,0x14,0xbb,0xae,0xe1,0xee,0x0a,0x64,0xeb,0xe2,0xd3,0x54,0xf5,0xb8,0xff,0x09,0x7d,0xf4,0x33,0xd4,0x5a,0x20,0x0b,0xdf,0x7d,0xc6,0xd1];
 const SESSION_ENDED_TOPIC: [u8; 32] = [0xd0,0x50,0xd1,0x0b,0x93,0x3c,0x19,0x15,0xcc,0x8e,0x44,0xa5,0x6b,0x9b,0x10,0xc1,0xfc,0x02,0x42,0xb0,0x3d,0x06,0x69,0x6c,0x69,0xcb,0x78,0x0b,0x76,0xa5,0x77,0xef];
 const AGENT_KICKED_TOPIC: [u8; 32] = [0xa4,0x54,0xdc,0xb3,0xcf,0x56,0x2a,0xd4,0xac,0x2b,0xe9,0x9f,0xcc,0xd0,0x85,0x9c,0x8e,0xae,0x16,0xa6,0x8e,0x13,0x0e,0x55,0x79,0x30,0x4f,0x5d,0x58,0xa8,0x55,0x2b];
+const HEARTBEAT_TOPIC: [u8; 32] = [0xd8,0x9b,0x4f,0x0f,0xb3,0x70,0x45,0xa4,0x30,0xd7,0xac,0x21,0x7a,0x24,0x37,0x7f,0x53,0x2b,0xf0,0x8e,0x29,0xe9,0xac,0x82,0x39,0x74,0xb0,0x6b,0xae,0xea,0xf0,0x0d];
+const BET_CANCELLED_TOPIC: [u8; 32] = [0xc4,0xfd,0xff,0xcf,0x77,0xf9,0x62,0x0f,0x64,0xa3,0x4a,0xdb,0x35,0x1d,0x71,0x2b,0x3f,0x76,0x9a,0x0a,0x45,0xe8,0xa8,0x1f,0xdc,0x8d,0x12,0xdf,0x75,0x1e,0x02,0x81];
+const FEE_PAID_TOPIC: [u8; 32] = [0x93,0xf5,0x32,0x45,0x34,0x25,0x08,0xa7,0x1e,0x17,0x2d,0x80,0x68,0xcb,0x5b,0x00,0x44,0xb5,0x3f,0xad,0x7b,0xd9,0xd8,0xce,0x2d,0xba,0xb4,0x0e,0x35,0x36,0xc1,0x40];
+const AGENT_LEFT_TOPIC: [u8; 32] = [0x8a,0x0f,0x3a,0xb8,0x4f,0x51,0x3c,0x48,0x3a,0x6b,0x0c,0xfc,0xf5,0x2f,0x55,0xbf,0x59,0xa4,0xef,0x8d,0x7b,0x5a,0x2c,0xda,0xee,0xa4,0xca,0x74,0xaa,0x73,0x0a,0xc1];
+const ANTES_COLLECTED_TOPIC: [u8; 32] = [0xae,0x3a,0x39,0x30,0xcd,0xce,0x02,0x98,0xd9,0x42,0x7c,0x1b,0x84,0x67,0x5a,0x6d,0xb2,0xda,0x4c,0x5e,0xb9,0xe9,0x14,0xe4,0x9f,0xd9,0x04,0x74,0xbb,0xe5,0xa2,0x84];
+const FEES_SWEPT_TOPIC: [u8; 32] = [0xb6,0xc8,0x1b,0x52,0x6e,0x1b,0xd5,0x5a,0x38,0x45,0xd8,0xca,0xa6,0xfe,0x0c,0x1c,0xe8,0x7b,0x3c,0x9e,0x53,0x4e,0xec,0x43,0xf4,0x3b,0x3c,0x18,0x49,0xd4,0xe2,0xe8];
+const PAUSED_TOPIC: [u8; 32] = [0x40,0xdb,0x37,0xff,0x5c,0x0b,0xdc,0x2c,0x42,0x7f,0xbb,0x20,0x78,0xc8,0xf2,0x4a,0xfe,0xa9,0x40,0xab,0xac,0x0e,0x3c,0x23,0xbb,0x4e,0xa3,0xbf,0x2d,0xa2,0xb2,0x12];
+const CHIPS_CLAIMED_TOPIC: [u8; 32] = [0x83,0xbc,0x42,0xef,0xd4,0xcc,0x82,0x58,0x20,0xfd,0xe7,0x12,0xb2,0xe5,0x31,0x42,0x6c,0xc7,0x68,0xe5,0xd1,0xa2,0xa6,0xfd,0x53,0xf2,0x41,0x5f,0xa1,0x6d,0x4d,0xc8];
+const PRIZE_CLAIMED_TOPIC: [u8; 32] = [0xc4,0xa2,0x29,0xab,0x15,0x21,0x3c,0x5d,0x32,0x5b,0xcf,0x4e,0xab,0x22,0x9c,0x92,0x29,0xe0,0x80,0x5c,0x34,0x11,0x9f,0x7e,0xce,0x2b,0xf0,0xcc,0xb6,0x39,0x55,0x8d];
+const BET_CLAIMED_TOPIC: [u8; 32] = [0xfd,0x10,0xa2,0x8b,0x91,0x64,0x3d,0xbc,0xc7,0x6b,0x8e,0x0d,0xe9,0x6f,0xbe,0xd3,0x9a,0x7c,0x16,0x5c,0x95,0x15,0xfa,0x7f,0xa9,0x0c,0x9d,0x1b,0x14,0x88,0xd6,0x96];
+const REBUY_TOPIC: [u8; 32] = [0x00,0xe3,0x3f,0x7a,0x5b,0x52,0x68,0xab,0xd3,0xd2,0x72,0x12,0xf4,0xbc,0x9d,0xb2,0x52,0xdb,0x49,0x82,0x1b,0x87,0x87,0xea,0x72,0x18,0x3e,0xca,0x60,0x75,0x76,0x16];
 
 // ---- Constants --------------------------------------------------------------
 const STATE_OPEN: u8 = 0;
@@ -51,10 +111,50 @@ const ACT_CALL: u8 = 2;
 const ACT_RAISE: u8 = 3;
 
 const MAX_AGENTS: u8 = 8;
+const NO_SEAT: u8 = 0xFF;             // no such seat (getSeatOf, find_active)
 const MAX_BETTORS: u8 = 64;
-const PLATFORM_FEE_BPS: u128 = 500;   // 5%
+const DEFAULT_PLATFORM_FEE_BPS: u32 = 500; // 5%, set at deploy
+const MAX_PLATFORM_FEE_BPS: u32 = 1_000;   // updateFee ceiling (10%)
+const MAX_TOTAL_FEE_BPS: u128 = 1_000; // platform + creator cut ceiling (10%)
+const DEFAULT_MIN_BUY_IN: u128 = 1;
+const DEFAULT_MAX_BUY_IN: u128 = u128::MAX >> 16; // headroom to sum 8 stacks + pools
 const INACTIVITY_SECS: u64 = 3600;    // 1 hour
+const MIN_INACTIVITY_SECS: u64 = 600; // floor for a per-table inactivity window
+const MAX_INACTIVITY_SECS: u64 = MAX_SESSION_SECS; // and a ceiling on it
 const MISSED_TURNS_KICK: u8 = 3;
+const MAX_SESSION_SECS: u64 = 7 * 24 * 3600; // longest per-table time budget (and the default)
+const MIN_HAND_SECS: u64 = 10;        // budget must allow session_len hands at this pace
+const MAX_BATCH: u32 = 16;            // tables per batchDeal / batchResolve
+const MAX_TABLES_PAGE: u32 = 16;      // summaries per getTables call
+const MAX_STATE_SCAN: u32 = 256;      // table ids examined per getTablesByState call
+
+// HAND_RESOLVED resolution type (last data byte)
+const RESOLVE_FOLD_WIN: u8 = 0; // everyone else folded
+const RESOLVE_DEALER: u8 = 1;   // showdown settled by the dealer
+const RESOLVE_SPLIT: u8 = 2;    // pot split between several winners
+const RESOLVE_CORRECTED: u8 = 3; // dealer moved a mis-resolved pot
+
+const DISPUTE_WINDOW_SECS: u64 = 120; // disputeResolve deadline after a resolve
+
+// getHandStatus per-seat flag bits
+const HS_DEALT_IN: u8 = 1;
+const HS_FOLDED: u8 = 2;
+const HS_ALL_IN: u8 = 4;  // dealt in, not folded, no chips behind
+const HS_ACTED: u8 = 8;
+const HS_KICKED: u8 = 16;
+
+// FEE_PAID pool byte
+const FEE_POOL_PRIZE: u8 = 0;
+const FEE_POOL_BETS: u8 = 1;
+
+// Where a kicked agent's chips go (per table, fixed at creation)
+const FORFEIT_TO_PRIZE: u8 = 0;  // added to the prize pool
+const FORFEIT_TO_AGENTS: u8 = 1; // shared pro rata by remaining agents' chips
+const FORFEIT_BURN: u8 = 2;      // left in the contract, unclaimable
+
+// Prize split when chip leaders tie (per-table, set at creation)
+const TIE_EVEN_SPLIT: u8 = 0;    // equal shares, remainder to the earliest seat
+const TIE_EARLIEST_SEAT: u8 = 1; // lowest tied seat takes the whole net pool
 
 // ============================================================================
 // STORAGE KEY LAYOUT
@@ -63,6 +163,16 @@ const MISSED_TURNS_KICK: u8 = 3;
 //   [0x01, 0..] -> table_count (u32)
 //   [0x02, 0..] -> platform_address ([u8;20])
 //   [0x03, 0..] -> dealer_address ([u8;20])
+//   [0x04, 0..] -> inactivity override secs (u64, 0 = use INACTIVITY_SECS)
+//   [0x05, 0..] -> missed-turns kick override (u8, 0 = use MISSED_TURNS_KICK)
+//   [0x06, 0..] -> platform fee bps (u32) for tables created from now on
+//   [0x07, 0..] -> total owed across all addresses (u128)
+//   [0x08, 0..] -> reentrancy lock (u8), held while a payout handler runs
+//   [0x09, token[20]] -> platform fees accrued, unswept (u128; zero token = native)
+//   [0x0A, 0..] -> paused (u8), blocks new tables, joins and bets
+//   [0x0B, 0..] -> min buy-in for new tables (u128)
+//   [0x0C, 0..] -> max buy-in for new tables (u128)
+//   [0x0D, token[20]] -> sweep epoch (u64), bumped by each sweep of that currency
 //
 // Per-table (prefix 0x10 + tableId[4]):
 //   field byte selects the value
@@ -75,17 +185,46 @@ const MISSED_TURNS_KICK: u8 = 3;
 //
 // Per-agent bet total (prefix 0x40 + tableId[4] + seat):
 //   single value -> u128
+//
+// Per-bettor running total (prefix 0x50 + tableId[4] + address[20]):
+//   single value -> u128, live (non-cancelled) bets across all seats
+//
+// Owed balance (prefix 0x60 + address[20]):
+//   single value -> u128, payouts whose transfer failed; see withdrawOwed
+//
+// Token owed balance (prefix 0x70 + tableId[4] + address[20]):
+//   single value -> u128, token payouts of that table whose transfer failed;
+//   see withdrawTokenOwed (a table's token is fixed, so this is per token)
 // ============================================================================
 
 fn key_table_count() -> [u8; 32] { let mut k = [0u8;32]; k[0]=0x01; k }
 fn key_platform()    -> [u8; 32] { let mut k = [0u8;32]; k[0]=0x02; k }
 fn key_dealer()      -> [u8; 32] { let mut k = [0u8;32]; k[0]=0x03; k }
+fn key_inactivity_override() -> [u8; 32] { let mut k = [0u8;32]; k[0]=0x04; k }
+fn key_kick_override()       -> [u8; 32] { let mut k = [0u8;32]; k[0]=0x05; k }
+fn key_platform_fee()        -> [u8; 32] { let mut k = [0u8;32]; k[0]=0x06; k }
+fn key_total_owed()          -> [u8; 32] { let mut k = [0u8;32]; k[0]=0x07; k }
+fn key_lock()                -> [u8; 32] { let mut k = [0u8;32]; k[0]=0x08; k }
+/// Platform fees accrued per currency; the zero address is the native token.
+fn key_platform_accrued(token: &[u8;20]) -> [u8; 32] {
+    let mut k = [0u8;32]; k[0]=0x09; k[1..21].copy_from_slice(token); k
+}
+fn key_paused()              -> [u8; 32] { let mut k = [0u8;32]; k[0]=0x0A; k }
+fn key_min_buy_in()          -> [u8; 32] { let mut k = [0u8;32]; k[0]=0x0B; k }
+fn key_max_buy_in()          -> [u8; 32] { let mut k = [0u8;32]; k[0]=0x0C; k }
+/// Sweeps so far per currency; a table's accruals are unswept while its
+/// TF_FEE_EPOCH still matches.
+fn key_sweep_epoch(token: &[u8;20]) -> [u8; 32] {
+    let mut k = [0u8;32]; k[0]=0x0D; k[1..21].copy_from_slice(token); k
+}
 
 fn key_table(tid: u32, f: u8) -> [u8; 32] {
     let mut k = [0u8;32]; k[0]=0x10;
     k[1..5].copy_from_slice(&tid.to_be_bytes()); k[5]=f; k
 }
 fn key_agent(tid: u32, seat: u8, f: u8) -> [u8; 32] {
+    // Invariant: seat byte must stay inside the fixed MAX_AGENTS layout
+    if seat >= MAX_AGENTS { revert(); }
     let mut k = [0u8;32]; k[0]=0x20;
     k[1..5].copy_from_slice(&tid.to_be_bytes()); k[5]=seat; k[6]=f; k
 }
@@ -94,9 +233,21 @@ fn key_bet(tid: u32, idx: u8, f: u8) -> [u8; 32] {
     k[1..5].copy_from_slice(&tid.to_be_bytes()); k[5]=idx; k[6]=f; k
 }
 fn key_agent_bet_total(tid: u32, seat: u8) -> [u8; 32] {
+    if seat >= MAX_AGENTS { revert(); }
     let mut k = [0u8;32]; k[0]=0x40;
     k[1..5].copy_from_slice(&tid.to_be_bytes()); k[5]=seat; k
 }
+fn key_bettor_total(tid: u32, addr: &[u8;20]) -> [u8; 32] {
+    let mut k = [0u8;32]; k[0]=0x50;
+    k[1..5].copy_from_slice(&tid.to_be_bytes()); k[5..25].copy_from_slice(addr); k
+}
+fn key_owed(addr: &[u8;20]) -> [u8; 32] {
+    let mut k = [0u8;32]; k[0]=0x60; k[1..21].copy_from_slice(addr); k
+}
+fn key_token_owed(tid: u32, addr: &[u8;20]) -> [u8; 32] {
+    let mut k = [0u8;32]; k[0]=0x70;
+    k[1..5].copy_from_slice(&tid.to_be_bytes()); k[5..25].copy_from_slice(addr); k
+}
 
 // ---- Table fields -----------------------------------------------------------
 const TF_CREATOR: u8 = 0;
@@ -117,22 +268,53 @@ const TF_BETTOR_COUNT: u8 = 14;
 const TF_LAST_ACTION: u8 = 15;   // u64 timestamp
 const TF_PRIZE_FEE_PAID: u8 = 16; // bool — platform fee sent for prize pool
 const TF_BET_FEE_PAID: u8 = 17;   // bool — platform fee sent for bet pool
+const TF_STARTED_AT: u8 = 18;     // u64 timestamp of the first deal
+const TF_FORFEIT_POLICY: u8 = 19; // FORFEIT_* — set at creation
+const TF_ALIVE_COUNT: u8 = 20;     // non-kicked agents, kept incrementally
+const TF_LAST_AWARD: u8 = 21;      // u128 pot of the last dealer resolve
+const TF_LAST_AWARD_SEAT: u8 = 22; // seat credited by the last dealer resolve
+const TF_LAST_AWARD_AT: u8 = 23;   // u64, 0 = nothing correctable
+const TF_REBUY_AFTER_KICK: u8 = 24; // bool — kicked agents may rebuy
+const TF_MIN_HANDS: u8 = 25;       // u32 — hands before kicks alone can end it
+const TF_CREATOR_FEE_BPS: u8 = 26; // u32 — creator cut of each pool
+const TF_SESSION_SECS: u8 = 27;    // u64 — time budget from the first deal
+const TF_TIE_POLICY: u8 = 28;      // TIE_* — prize split among tied leaders
+const TF_SMALL_BLIND: u8 = 29;     // u128 — forced bet left of the button
+const TF_BIG_BLIND: u8 = 30;       // u128 — forced bet left of the small blind
+const TF_BUTTON: u8 = 31;          // seat holding the dealer button this hand
+const TF_LAST_RAISE: u8 = 32;      // u128 — size of the last full raise (BB at deal)
+const TF_PLATFORM_FEE_BPS: u8 = 33; // u32 — global platform fee snapshotted at creation
+const TF_ANTE: u8 = 34;            // u128 — posted by every dealt-in seat each hand
+const TF_TOKEN: u8 = 35;           // address — ERC-20 buy-in/prize/bet token (zero = native)
+const TF_INACTIVITY: u8 = 36;      // u64 — dealer-inactivity window (0 = global)
+const TF_TURN_TIMEOUT: u8 = 37;    // u64 — secs an agent gets per turn (0 = dealer-only timeouts)
+const TF_TURN_DEADLINE: u8 = 38;   // u64 — when the current turn can be force-folded
+const TF_KICK_THRESHOLD: u8 = 39;  // u8 — timeouts before a kick (0 = global)
+const TF_FEE_ACCRUED: u8 = 40;     // u128 — platform fees this table accrued in epoch TF_FEE_EPOCH
+const TF_FEE_EPOCH: u8 = 41;       // u64 — sweep epoch of the table's currency at its last accrual
 
 // ---- Agent fields -----------------------------------------------------------
 const AF_ADDR: u8 = 0;
 const AF_CHIPS: u8 = 1;
 const AF_FOLDED: u8 = 2;
 const AF_KICKED: u8 = 3;
-const AF_MISSED: u8 = 4;
+const AF_MISSED: u8 = 4;    // consecutive timeouts, see record_miss
 const AF_HAND_BET: u8 = 5;  // chips committed to current hand's pot
 const AF_CHIPS_CLAIMED: u8 = 6;
 const AF_PRIZE_CLAIMED: u8 = 7;
+const AF_DEALT_IN: u8 = 8;  // dealt into the current hand
+const AF_ACTED: u8 = 9;     // acted (or timed out) this hand
+const AF_SINCE_RAISE: u8 = 10;  // acted since the last full raise
+const AF_RAISE_LOCKED: u8 = 11; // faces a short all-in after acting: call/fold only
+const AF_ANTE_PAID: u8 = 12;    // u128 — ante posted this hand (dead money, outside AF_HAND_BET)
+const AF_REBOUGHT: u8 = 13;     // u128 — total added by rebuys this session
 
 // ---- Bet fields -------------------------------------------------------------
 const BF_ADDR: u8 = 0;
 const BF_SEAT: u8 = 1;
 const BF_AMOUNT: u8 = 2;
 const BF_CLAIMED: u8 = 3;
+const BF_CANCELLED: u8 = 4;  // withdrawn by the bettor before the deadline
 
 // ============================================================================
 // STORAGE HELPERS
@@ -202,16 +384,37 @@ fn get_now() -> u64 {
 
 fn addr_eq(a: &[u8;20], b: &[u8;20]) -> bool { *a == *b }
 
+// Reentrancy guard. call() takes the lock for every handler that moves
+// value and rejects any call at all while it is held; the return helpers
+// release it. Each call frame runs in fresh memory, so HOLDS_LOCK marks only
+// the frame that took it — a nested call can never release the outer lock.
+// A revert rolls the lock back along with everything else.
+static mut HOLDS_LOCK: bool = false;
+
+fn lock() {
+    store_u8(&key_lock(), 1);
+    unsafe { HOLDS_LOCK = true; }
+}
+
+fn unlock() {
+    if unsafe { HOLDS_LOCK } { store_u8(&key_lock(), 0); }
+}
+
 fn revert() -> ! { api::return_value(ReturnFlags::REVERT, &[]); }
 
-fn return_empty() -> ! { api::return_value(ReturnFlags::empty(), &[]); }
+fn return_empty() -> ! {
+    unlock();
+    api::return_value(ReturnFlags::empty(), &[]);
+}
 
 fn return_u8(v: u8) -> ! {
+    unlock();
     let mut r=[0u8;32]; r[31]=v;
     api::return_value(ReturnFlags::empty(), &r);
 }
 
 fn return_u32(v: u32) -> ! {
+    unlock();
     let mut r=[0u8;32]; r[28..].copy_from_slice(&v.to_be_bytes());
     api::return_value(ReturnFlags::empty(), &r);
 }
@@ -232,26 +435,231 @@ fn read_u64_param(offset: u32) -> u64 {
 fn read_addr_param(offset: u32) -> [u8;20] {
     let w=read_word(offset); let mut a=[0u8;20]; a.copy_from_slice(&w[12..]); a
 }
+/// Dynamic array param: returns (calldata offset of element 0, length).
+/// Element i lives at `start + 32*i`.
+fn read_array_param(offset: u32) -> (u32, u32) {
+    let head = 4 + read_u32_param(offset);
+    (head + 32, read_u32_param(head))
+}
 
+/// Push `amount` to `addr`. If the call fails (e.g. a contract with no
+/// payable fallback) the amount is credited to the address's owed balance
+/// instead, so every claim path can still mark itself done without losing
+/// funds; the recipient pulls it later with withdrawOwed.
 fn transfer_to(addr: &[u8;20], amount: u128) {
     if amount == 0 { return; }
+    if send_value(addr, amount) { return; }
+    let k = key_owed(addr);
+    store_u128(&k, load_u128(&k) + amount);
+    let t = key_total_owed();
+    store_u128(&t, load_u128(&t) + amount);
+}
+
+fn send_value(addr: &[u8;20], amount: u128) -> bool {
     let mut value=[0u8;32]; value[16..].copy_from_slice(&amount.to_be_bytes());
     let deposit=[0u8;32];
-    let _ = api::call(
+    api::call(
         pallet_revive_uapi::CallFlags::empty(),
         addr, 0, 0, &deposit, &value, &[], None,
-    );
+    ).is_ok()
+}
+
+const ERC20_TRANSFER: [u8; 4]      = [0xa9, 0x05, 0x9c, 0xbb]; // transfer(address,uint256)
+const ERC20_TRANSFER_FROM: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd]; // transferFrom(address,address,uint256)
+const ERC20_BALANCE_OF: [u8; 4]    = [0x70, 0xa0, 0x82, 0x31]; // balanceOf(address)
+
+/// Whether `addr` holds contract code. A call to an address without any
+/// succeeds with empty return data, which token_call would read as a
+/// transfer that went through.
+fn has_code(addr: &[u8;20]) -> bool {
+    let mut v=[0u8;32]; api::code_size(addr, &mut v);
+    v != [0u8;32]
+}
+
+/// Call an ERC-20 method that returns bool. Tokens that return nothing are
+/// accepted as long as the call itself succeeded (createTable only admits
+/// token addresses that hold code).
+fn token_call(token: &[u8;20], input: &[u8]) -> bool {
+    let deposit=[0u8;32];
+    let value=[0u8;32];
+    let mut out=[0u8;32];
+    let mut o=&mut out[..];
+    if api::call(
+        pallet_revive_uapi::CallFlags::empty(),
+        token, 0, 0, &deposit, &value, input, Some(&mut o),
+    ).is_err() { return false; }
+    o.is_empty() || o[31] == 1
+}
+
+fn token_transfer(token: &[u8;20], to: &[u8;20], amount: u128) -> bool {
+    let mut input=[0u8;68];
+    input[0..4].copy_from_slice(&ERC20_TRANSFER);
+    input[16..36].copy_from_slice(to);
+    input[52..68].copy_from_slice(&amount.to_be_bytes());
+    token_call(token, &input)
+}
+
+/// `who`'s balance of `token`; reverts if the token doesn't answer with a
+/// uint256 that fits u128.
+fn token_balance(token: &[u8;20], who: &[u8;20]) -> u128 {
+    let mut input=[0u8;36];
+    input[0..4].copy_from_slice(&ERC20_BALANCE_OF);
+    input[16..36].copy_from_slice(who);
+    let deposit=[0u8;32];
+    let value=[0u8;32];
+    let mut out=[0u8;32];
+    let mut o=&mut out[..];
+    if api::call(
+        pallet_revive_uapi::CallFlags::empty(),
+        token, 0, 0, &deposit, &value, &input, Some(&mut o),
+    ).is_err() { revert(); }
+    if o.len() < 32 || o[..16] != [0u8;16] { revert(); }
+    u128::from_be_bytes(o[16..32].try_into().unwrap())
+}
+
+/// Pull `amount` of the table's token from `from` into the contract (needs
+/// a prior approve). Reverts the whole call if the token refuses, or if
+/// less than `amount` arrives (fee-on-transfer tokens): crediting the full
+/// amount would pay the shortfall out of other tables' tokens.
+fn token_pull(token: &[u8;20], from: &[u8;20], amount: u128) {
+    let mut me=[0u8;20]; api::address(&mut me);
+    let before = token_balance(token, &me);
+    let mut input=[0u8;100];
+    input[0..4].copy_from_slice(&ERC20_TRANSFER_FROM);
+    input[16..36].copy_from_slice(from);
+    input[48..68].copy_from_slice(&me);
+    input[84..100].copy_from_slice(&amount.to_be_bytes());
+    if !token_call(token, &input) { revert(); }
+    if token_balance(token, &me).saturating_sub(before) < amount { revert(); }
+}
+
+/// Take a table deposit: msg.value on native tables, a transferFrom of the
+/// table's token otherwise (msg.value must then be 0).
+fn collect_in(tid: u32, from: &[u8;20], amount: u128) {
+    let token = load_addr(&key_table(tid, TF_TOKEN));
+    if token == [0u8;20] {
+        if get_value() != amount { revert(); }
+    } else {
+        if get_value() != 0 { revert(); }
+        token_pull(&token, from, amount);
+    }
+}
+
+/// Pay out of a table in its currency. A failed transfer never blocks the
+/// claim: native payouts fall back to the owed balance, token payouts (a
+/// blacklisted recipient, a hook that reverts) to the table's token owed
+/// balance, pulled later with withdrawTokenOwed.
+fn pay_out(tid: u32, to: &[u8;20], amount: u128) {
+    if amount == 0 { return; }
+    let token = load_addr(&key_table(tid, TF_TOKEN));
+    if token == [0u8;20] { transfer_to(to, amount); return; }
+    if token_transfer(&token, to, amount) { return; }
+    let k = key_token_owed(tid, to);
+    store_u128(&k, load_u128(&k) + amount);
 }
 
 fn emit(topic: &[u8;32], data: &[u8]) {
     api::deposit_event(&[*topic], data);
 }
 
+/// Seat held by `addr` on `tid`, or NO_SEAT
+fn seat_of(tid: u32, addr: &[u8;20]) -> u8 {
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let mut i: u8 = 0;
+    while i < count {
+        if addr_eq(&load_addr(&key_agent(tid, i, AF_ADDR)), addr) { return i; }
+        i += 1;
+    }
+    NO_SEAT
+}
+
+/// A bet that is neither paid out nor cancelled by its bettor
+fn bet_is_live(tid: u32, idx: u8) -> bool {
+    load_u8(&key_bet(tid, idx, BF_CLAIMED)) == 0 &&
+    load_u8(&key_bet(tid, idx, BF_CANCELLED)) == 0
+}
+
+/// Past the table's time budget (counted from the first deal). The three
+/// session clocks nest: each hand must keep inactivity under the cancel
+/// window, the whole session must fit the budget, and hands run out at
+/// session_len — whichever comes first ends or cancels the table.
+fn budget_spent(tid: u32, now: u64) -> bool {
+    let started = load_u64(&key_table(tid, TF_STARTED_AT));
+    started != 0 && now.saturating_sub(started) > load_u64(&key_table(tid, TF_SESSION_SECS))
+}
+
 /// Refresh the inactivity timer (called on every dealer action)
 fn touch_last_action(tid: u32) {
     store_u64(&key_table(tid, TF_LAST_ACTION), get_now());
 }
 
+/// Platform cut of a pool at the rate the table was created with, so a
+/// later updateFee can't change what an existing table's players signed up for.
+fn platform_fee_of(tid: u32, amount: u128) -> u128 {
+    amount * load_u32(&key_table(tid, TF_PLATFORM_FEE_BPS)) as u128 / 10_000
+}
+
+/// Table creator's cut of a pool, bounded at creation so that platform +
+/// creator never exceeds MAX_TOTAL_FEE_BPS.
+fn creator_fee_of(tid: u32, amount: u128) -> u128 {
+    amount * load_u32(&key_table(tid, TF_CREATOR_FEE_BPS)) as u128 / 10_000
+}
+
+/// What's left of a pool for winners after both fees.
+fn net_of_fees(tid: u32, amount: u128) -> u128 {
+    amount - platform_fee_of(tid, amount) - creator_fee_of(tid, amount)
+}
+
+/// Take the platform and creator cuts of a pool (caller guards "once").
+/// The platform cut accrues until sweepFees; the creator cut is sent now.
+fn pay_pool_fees(tid: u32, amount: u128, pool: u8) {
+    let fee = platform_fee_of(tid, amount);
+    let creator_fee = creator_fee_of(tid, amount);
+    let token = load_addr(&key_table(tid, TF_TOKEN));
+    let acc = key_platform_accrued(&token);
+    store_u128(&acc, load_u128(&acc) + fee);
+    // Per-table share of `acc`, restarted once a sweep has collected it
+    let epoch = load_u64(&key_sweep_epoch(&token));
+    let mut table_acc = load_u128(&key_table(tid, TF_FEE_ACCRUED));
+    if load_u64(&key_table(tid, TF_FEE_EPOCH)) != epoch { table_acc = 0; }
+    store_u128(&key_table(tid, TF_FEE_ACCRUED), table_acc + fee);
+    store_u64 (&key_table(tid, TF_FEE_EPOCH), epoch);
+    if creator_fee > 0 {
+        pay_out(tid, &load_addr(&key_table(tid, TF_CREATOR)), creator_fee);
+    }
+
+    let mut data = [0u8; 37];
+    data[0..4].copy_from_slice(&tid.to_be_bytes());
+    data[4] = pool;
+    data[5..21].copy_from_slice(&fee.to_be_bytes());
+    data[21..37].copy_from_slice(&creator_fee.to_be_bytes());
+    emit(&FEE_PAID_TOPIC, &data);
+}
+
+/// Effective dealer-inactivity window: platform override, else the default
+fn inactivity_secs() -> u64 {
+    let o = load_u64(&key_inactivity_override());
+    if o != 0 { o } else { INACTIVITY_SECS }
+}
+
+/// A table's own kick threshold if it set one at creation, else the global
+fn table_kick_threshold(tid: u32) -> u8 {
+    let t = load_u8(&key_table(tid, TF_KICK_THRESHOLD));
+    if t != 0 { t } else { kick_threshold() }
+}
+
+/// A table's own inactivity window if it set one at creation, else the global
+fn table_inactivity_secs(tid: u32) -> u64 {
+    let t = load_u64(&key_table(tid, TF_INACTIVITY));
+    if t != 0 { t } else { inactivity_secs() }
+}
+
+/// Effective missed-turns kick threshold: platform override, else the default
+fn kick_threshold() -> u8 {
+    let o = load_u8(&key_kick_override());
+    if o != 0 { o } else { MISSED_TURNS_KICK }
+}
+
 // ============================================================================
 // PANIC HANDLER
 // ============================================================================
@@ -270,12 +678,25 @@ pub extern "C" fn deploy() {
     store_addr(&key_platform(), &caller);
     store_addr(&key_dealer(), &caller);   // platform = first dealer
     store_u32(&key_table_count(), 0);
+    store_u32(&key_platform_fee(), DEFAULT_PLATFORM_FEE_BPS);
+    store_u128(&key_min_buy_in(), DEFAULT_MIN_BUY_IN);
+    store_u128(&key_max_buy_in(), DEFAULT_MAX_BUY_IN);
 }
 
 #[polkavm_derive::polkavm_export]
 pub extern "C" fn call() {
     if api::call_data_size() < 4 { revert(); }
     let mut sel=[0u8;4]; api::call_data_copy(&mut sel, 0);
+    if load_u8(&key_lock()) != 0 { revert(); }
+    if matches!(sel,
+        CLAIM_CHIPS_SEL | CLAIM_PRIZE_SEL | CLAIM_BET_WINNINGS_SEL | CLAIM_ALL_BETS_SEL |
+        REFUND_SEL | CANCEL_BET_SEL | LEAVE_TABLE_SEL | WITHDRAW_OWED_SEL |
+        WITHDRAW_BET_SEL | CLAIM_ALL_SEL | SWEEP_FEES_SEL | SWEEP_TOKEN_FEES_SEL |
+        WITHDRAW_TOKEN_OWED_SEL |
+        // these call out to the table's token before their writes
+        CREATE_TABLE_SEL | JOIN_TABLE_SEL | PLACE_BET_TOKEN_SEL | REBUY_SEL) {
+        lock();
+    }
     match sel {
         CREATE_TABLE_SEL        => handle_create_table(),
         JOIN_TABLE_SEL          => handle_join_table(),
@@ -296,6 +717,55 @@ pub extern "C" fn call() {
         GET_AGENT_BET_TOTAL_SEL => handle_get_agent_bet_total(),
         GET_TOTAL_BET_POOL_SEL  => handle_get_total_bet_pool(),
         GET_CURRENT_POT_SEL     => handle_get_current_pot(),
+        SET_GLOBAL_THRESHOLDS_SEL => handle_set_global_thresholds(),
+        HEARTBEAT_SEL           => handle_heartbeat(),
+        GET_FEE_STATUS_SEL      => handle_get_fee_status(),
+        CANCEL_BET_SEL          => handle_cancel_bet(),
+        BATCH_DEAL_SEL          => handle_batch_deal(),
+        BATCH_RESOLVE_SEL       => handle_batch_resolve(),
+        GET_BETTOR_TOTAL_SEL    => handle_get_bettor_total(),
+        GET_BET_BY_INDEX_SEL    => handle_get_bet_by_index(),
+        GET_TABLES_SEL          => handle_get_tables(),
+        QUOTE_BET_SEL           => handle_quote_bet(),
+        GET_GAME_STATE_SEL      => handle_get_game_state(),
+        DISPUTE_RESOLVE_SEL     => handle_dispute_resolve(),
+        GET_HAND_STATUS_SEL     => handle_get_hand_status(),
+        IS_MY_TURN_SEL          => handle_is_my_turn(),
+        GET_TABLE_LIABILITIES_SEL => handle_get_table_liabilities(),
+        GET_FINAL_RESULTS_SEL   => handle_get_final_results(),
+        CLAIM_ALL_BETS_SEL      => handle_claim_all_bets(),
+        RESOLVE_HAND_MULTI_SEL  => handle_resolve_hand_multi(),
+        LEAVE_TABLE_SEL         => handle_leave_table(),
+        GET_TABLES_BY_STATE_SEL => handle_get_tables_by_state(),
+        GET_ALL_AGENTS_SEL      => handle_get_all_agents(),
+        UPDATE_FEE_SEL          => handle_update_fee(),
+        WITHDRAW_OWED_SEL       => handle_withdraw_owed(),
+        GET_OWED_SEL            => handle_get_owed(),
+        GET_ROUND_COMPLETE_SEL  => handle_get_round_complete(),
+        ADVANCE_ROUND_SEL       => handle_advance_round(),
+        WITHDRAW_BET_SEL        => handle_withdraw_bet(),
+        CLAIM_ALL_SEL           => handle_claim_all(),
+        SWEEP_FEES_SEL          => handle_sweep_fees(),
+        GET_PLATFORM_ACCRUED_SEL => handle_get_platform_accrued(),
+        PLACE_BET_TOKEN_SEL     => handle_place_bet_token(),
+        SWEEP_TOKEN_FEES_SEL    => handle_sweep_token_fees(),
+        GET_TABLE_TOKEN_SEL     => handle_get_table_token(),
+        SET_PAUSED_SEL          => handle_set_paused(),
+        GET_PAUSED_SEL          => handle_get_paused(),
+        SET_BUY_IN_BOUNDS_SEL   => handle_set_buy_in_bounds(),
+        GET_BUY_IN_BOUNDS_SEL   => handle_get_buy_in_bounds(),
+        GET_SEAT_OF_SEL         => handle_get_seat_of(),
+        SET_TURN_TIMEOUT_SEL    => handle_set_turn_timeout(),
+        FORCE_FOLD_SEL          => handle_force_fold(),
+        GET_TURN_DEADLINE_SEL   => handle_get_turn_deadline(),
+        GET_DEALER_SEL          => handle_get_dealer(),
+        GET_PLATFORM_SEL        => handle_get_platform(),
+        REBUY_SEL               => handle_rebuy(),
+        GET_CLAIMABLE_SEL       => handle_get_claimable(),
+        GET_TABLE_TVL_SEL       => handle_get_table_tvl(),
+        GET_TABLE_COUNT_SEL     => handle_get_table_count(),
+        WITHDRAW_TOKEN_OWED_SEL => handle_withdraw_token_owed(),
+        GET_TOKEN_OWED_SEL      => handle_get_token_owed(),
         _                       => revert(),
     }
 }
@@ -304,31 +774,94 @@ pub extern "C" fn call() {
 // TABLE MANAGEMENT
 // ============================================================================
 
+/// New money can't enter while the platform has the contract paused; exits
+/// (claims, refunds, cancel) stay open.
+fn require_not_paused() {
+    if load_u8(&key_paused()) != 0 { revert(); }
+}
+
 fn handle_create_table() -> ! {
+    require_not_paused();
     let buy_in       = read_u256_as_u128(4);
     let max_agents   = read_u8_param(36);
     let session_len  = read_u32_param(68);
     let bet_deadline = read_u64_param(100);
+    let forfeit      = read_u8_param(132);
+    let rebuy_kicked = read_u8_param(164);
+    let min_hands    = read_u32_param(196);
+    let creator_bps  = read_u32_param(228);
+    let mut budget   = read_u64_param(260);
+    let tie_policy   = read_u8_param(292);
+    let small_blind  = read_u256_as_u128(324);
+    let big_blind    = read_u256_as_u128(356);
+    let ante         = read_u256_as_u128(388);
+    let token        = read_addr_param(420);
+    let token_pool   = read_u256_as_u128(452);
+    let inactivity   = read_u64_param(484);
+    let kick_after   = read_u8_param(516);
 
     if buy_in == 0                    { revert(); }
+    if buy_in < load_u128(&key_min_buy_in()) || buy_in > load_u128(&key_max_buy_in()) { revert(); }
     if max_agents < 2 || max_agents > MAX_AGENTS { revert(); }
     if session_len == 0               { revert(); }
     if bet_deadline <= get_now()      { revert(); }
-
-    // msg.value IS the prize pool
-    let prize_pool = get_value();
+    if forfeit > FORFEIT_BURN         { revert(); }
+    if rebuy_kicked > 1               { revert(); }
+    if tie_policy > TIE_EARLIEST_SEAT { revert(); }
+    if small_blind > big_blind || big_blind > buy_in { revert(); }
+    if ante > buy_in                  { revert(); }
+    if min_hands > session_len        { revert(); }
+    // 0 = follow the global window; otherwise short enough windows would
+    // let anyone cancel a live table between two dealer txs, and an endless
+    // one would lock every deposit if the dealer disappeared
+    if inactivity != 0 &&
+       (inactivity < MIN_INACTIVITY_SECS || inactivity > MAX_INACTIVITY_SECS) { revert(); }
+    if token != [0u8;20] && !has_code(&token) { revert(); }
+    // creator_bps is a uint16 in the ABI but read as a full word: bound it
+    // before any arithmetic, and sum in u128 so nothing can wrap
+    if creator_bps as u128 > MAX_TOTAL_FEE_BPS { revert(); }
+    let platform_bps = load_u32(&key_platform_fee());
+    if platform_bps as u128 + creator_bps as u128 > MAX_TOTAL_FEE_BPS { revert(); }
+    // Time budget: 0 = the maximum. It has to fit session_len hands, or the
+    // table is configured to always end on the clock rather than on hands.
+    if budget == 0 { budget = MAX_SESSION_SECS; }
+    if budget > MAX_SESSION_SECS      { revert(); }
+    if (session_len as u64) * MIN_HAND_SECS > budget { revert(); }
+
+    // msg.value IS the prize pool, or tokenPrizePool on a token table
+    let prize_pool = if token == [0u8;20] {
+        if token_pool != 0            { revert(); }
+        get_value()
+    } else {
+        if get_value() != 0           { revert(); }
+        token_pool
+    };
     if prize_pool == 0                { revert(); }
+    // Pool must be big enough that the fee and every tied winner's share
+    // survive integer truncation (e.g. >= 20 wei at 5% for the fee)
+    let fee = prize_pool * platform_bps as u128 / 10_000;
+    if platform_bps > 0 && fee == 0   { revert(); }
+    let creator_fee = prize_pool * creator_bps as u128 / 10_000;
+    let net = match prize_pool.checked_sub(fee).and_then(|p| p.checked_sub(creator_fee)) {
+        Some(n) => n,
+        None => revert(),
+    };
+    if net / (max_agents as u128) == 0 { revert(); }
 
     let caller = get_caller();
+    if token != [0u8;20] { token_pull(&token, &caller, prize_pool); }
     let tc_key = key_table_count();
     let tid = load_u32(&tc_key);
-    store_u32(&tc_key, tid + 1);
+    // Wrapping would reuse table 0's storage
+    let next = match tid.checked_add(1) { Some(n) => n, None => revert() };
+    store_u32(&tc_key, next);
 
     store_addr(&key_table(tid, TF_CREATOR),       &caller);
     store_u128(&key_table(tid, TF_PRIZE_POOL),    prize_pool);
     store_u128(&key_table(tid, TF_BUY_IN),        buy_in);
     store_u8  (&key_table(tid, TF_MAX_AGENTS),    max_agents);
     store_u8  (&key_table(tid, TF_AGENT_COUNT),   0);
+    store_u8  (&key_table(tid, TF_ALIVE_COUNT),   0);
     store_u32 (&key_table(tid, TF_SESSION_LEN),   session_len);
     store_u32 (&key_table(tid, TF_CURRENT_HAND),  0);
     store_u64 (&key_table(tid, TF_BET_DEADLINE),  bet_deadline);
@@ -339,17 +872,35 @@ fn handle_create_table() -> ! {
     store_u64 (&key_table(tid, TF_LAST_ACTION),   get_now());
     store_u8  (&key_table(tid, TF_PRIZE_FEE_PAID),0);
     store_u8  (&key_table(tid, TF_BET_FEE_PAID),  0);
+    store_u64 (&key_table(tid, TF_STARTED_AT),    0);
+    store_u8  (&key_table(tid, TF_FORFEIT_POLICY),forfeit);
+    store_u8  (&key_table(tid, TF_REBUY_AFTER_KICK), rebuy_kicked);
+    store_u32 (&key_table(tid, TF_MIN_HANDS),     min_hands);
+    store_u32 (&key_table(tid, TF_CREATOR_FEE_BPS), creator_bps);
+    store_u32 (&key_table(tid, TF_PLATFORM_FEE_BPS), platform_bps);
+    store_u64 (&key_table(tid, TF_SESSION_SECS),  budget);
+    store_u8  (&key_table(tid, TF_TIE_POLICY),    tie_policy);
+    store_u128(&key_table(tid, TF_SMALL_BLIND),   small_blind);
+    store_u128(&key_table(tid, TF_BIG_BLIND),     big_blind);
+    store_u128(&key_table(tid, TF_ANTE),          ante);
+    store_addr(&key_table(tid, TF_TOKEN),         &token);
+    store_u64 (&key_table(tid, TF_INACTIVITY),    inactivity);
+    store_u8  (&key_table(tid, TF_KICK_THRESHOLD), kick_after);
 
     emit(&TABLE_CREATED_TOPIC, &tid.to_be_bytes());
     return_u32(tid);
 }
 
 fn handle_join_table() -> ! {
+    require_not_paused();
     let tid = read_u32_param(4);
     if load_u8(&key_table(tid, TF_STATE)) != STATE_OPEN { revert(); }
+    // Roster freezes with the betting window: bets were placed on this
+    // field. deal() doesn't wait for the deadline, so a table that filled
+    // up in time can still start as soon as the dealer is ready.
+    if get_now() >= load_u64(&key_table(tid, TF_BET_DEADLINE)) { revert(); }
 
     let buy_in = load_u128(&key_table(tid, TF_BUY_IN));
-    if get_value() != buy_in { revert(); }
 
     let agent_count = load_u8(&key_table(tid, TF_AGENT_COUNT));
     let max = load_u8(&key_table(tid, TF_MAX_AGENTS));
@@ -364,6 +915,8 @@ fn handle_join_table() -> ! {
     }
 
     let seat = agent_count;
+    if seat >= MAX_AGENTS { revert(); }
+    collect_in(tid, &caller, buy_in);
     store_addr(&key_agent(tid, seat, AF_ADDR),         &caller);
     store_u128(&key_agent(tid, seat, AF_CHIPS),        buy_in);
     store_u8  (&key_agent(tid, seat, AF_FOLDED),       0);
@@ -373,22 +926,129 @@ fn handle_join_table() -> ! {
     store_u8  (&key_agent(tid, seat, AF_CHIPS_CLAIMED),0);
     store_u8  (&key_agent(tid, seat, AF_PRIZE_CLAIMED),0);
     store_u8  (&key_table(tid, TF_AGENT_COUNT), seat + 1);
+    let alive = load_u8(&key_table(tid, TF_ALIVE_COUNT));
+    store_u8  (&key_table(tid, TF_ALIVE_COUNT), alive + 1);
 
     emit(&AGENT_JOINED_TOPIC, &[seat]);
     return_u8(seat);
 }
 
+/// A busted agent buys back in for another buy_in between hands of a
+/// Playing session. A kicked agent may too if the table allows rebuys
+/// after a kick: that clears the kick and the missed-turn streak and puts
+/// it back in the alive count. The money becomes chips like the original
+/// buy-in — it circulates through pots and comes back out via claimChips
+/// (or the cancel refund), so the prize pool and prize math don't change.
+/// REBUY data: tableId(4) | seat | amount (u128).
+fn handle_rebuy() -> ! {
+    require_not_paused();
+    let tid = read_u32_param(4);
+    if load_u8(&key_table(tid, TF_STATE)) != STATE_PLAYING { revert(); }
+    if load_u128(&key_table(tid, TF_POT)) != 0 { revert(); }
+
+    let caller = get_caller();
+    let seat = seat_of(tid, &caller);
+    if seat == NO_SEAT { revert(); }
+    if load_u128(&key_agent(tid, seat, AF_CHIPS)) != 0 { revert(); }
+
+    if load_u8(&key_agent(tid, seat, AF_KICKED)) != 0 {
+        if load_u8(&key_table(tid, TF_REBUY_AFTER_KICK)) == 0 { revert(); }
+        store_u8(&key_agent(tid, seat, AF_KICKED), 0);
+        clear_misses(tid, seat);
+        let alive = load_u8(&key_table(tid, TF_ALIVE_COUNT));
+        store_u8(&key_table(tid, TF_ALIVE_COUNT), alive + 1);
+    }
+
+    let buy_in = load_u128(&key_table(tid, TF_BUY_IN));
+    collect_in(tid, &caller, buy_in);
+    store_u128(&key_agent(tid, seat, AF_CHIPS), buy_in);
+    let rb = key_agent(tid, seat, AF_REBOUGHT);
+    store_u128(&rb, load_u128(&rb) + buy_in);
+
+    let mut data = [0u8; 21];
+    data[0..4].copy_from_slice(&tid.to_be_bytes());
+    data[4] = seat;
+    data[5..21].copy_from_slice(&buy_in.to_be_bytes());
+    emit(&REBUY_TOPIC, &data);
+    return_empty();
+}
+
+/// Agent takes its buy-in back from an Open table before the first deal.
+/// Bets on its seat are refunded, then the last seat moves into the hole so
+/// seats stay 0..agentCount with no gaps; bets backing the moved agent
+/// follow it. AGENT_LEFT data: seat | seat the new occupant moved from
+/// (== seat when the leaver was last).
+fn handle_leave_table() -> ! {
+    let tid = read_u32_param(4);
+    if load_u8(&key_table(tid, TF_STATE)) != STATE_OPEN { revert(); }
+    if load_u32(&key_table(tid, TF_CURRENT_HAND)) != 0  { revert(); }
+
+    let caller = get_caller();
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let mut seat: u8 = 0;
+    while seat < count && !addr_eq(&load_addr(&key_agent(tid, seat, AF_ADDR)), &caller) {
+        seat += 1;
+    }
+    if seat == count { revert(); }
+
+    refund_seat_bets(tid, seat);
+    let chips = load_u128(&key_agent(tid, seat, AF_CHIPS));
+
+    let last = count - 1;
+    if seat != last {
+        store_addr(&key_agent(tid, seat, AF_ADDR),  &load_addr(&key_agent(tid, last, AF_ADDR)));
+        store_u128(&key_agent(tid, seat, AF_CHIPS), load_u128(&key_agent(tid, last, AF_CHIPS)));
+        store_u128(&key_agent_bet_total(tid, seat), load_u128(&key_agent_bet_total(tid, last)));
+        let bettors = load_u8(&key_table(tid, TF_BETTOR_COUNT));
+        let mut j: u8 = 0;
+        while j < bettors {
+            if load_u8(&key_bet(tid, j, BF_SEAT)) == last {
+                store_u8(&key_bet(tid, j, BF_SEAT), seat);
+            }
+            j += 1;
+        }
+    }
+    store_addr(&key_agent(tid, last, AF_ADDR),  &[0u8; 20]);
+    store_u128(&key_agent(tid, last, AF_CHIPS), 0);
+    store_u128(&key_agent_bet_total(tid, last), 0);
+    store_u8(&key_table(tid, TF_AGENT_COUNT), last);
+    let alive = load_u8(&key_table(tid, TF_ALIVE_COUNT));
+    store_u8(&key_table(tid, TF_ALIVE_COUNT), alive - 1);
+
+    emit(&AGENT_LEFT_TOPIC, &[seat, last]);
+    pay_out(tid, &caller, chips);
+    return_empty();
+}
+
 fn handle_place_bet() -> ! {
     let tid = read_u32_param(4);
     let seat = read_u8_param(36);
+    if load_addr(&key_table(tid, TF_TOKEN)) != [0u8;20] { revert(); }
+    record_bet(tid, seat, get_value());
+}
+
+/// placeBet for token tables: the stake is pulled with transferFrom.
+fn handle_place_bet_token() -> ! {
+    let tid = read_u32_param(4);
+    let seat = read_u8_param(36);
+    let amount = read_u256_as_u128(68);
+    let token = load_addr(&key_table(tid, TF_TOKEN));
+    if token == [0u8;20] || get_value() != 0 { revert(); }
+    if amount != 0 { token_pull(&token, &get_caller(), amount); }
+    record_bet(tid, seat, amount);
+}
 
+/// Validate and book a bet whose stake has already been taken.
+fn record_bet(tid: u32, seat: u8, amount: u128) -> ! {
+    require_not_paused();
     if load_u8(&key_table(tid, TF_STATE)) != STATE_OPEN   { revert(); }
     if get_now() >= load_u64(&key_table(tid, TF_BET_DEADLINE)) { revert(); }
     if seat >= load_u8(&key_table(tid, TF_AGENT_COUNT))   { revert(); }
+    // Can't bet on a vacated seat
+    if load_addr(&key_agent(tid, seat, AF_ADDR)) == [0u8;20] { revert(); }
     // Can't bet on a kicked agent
     if load_u8(&key_agent(tid, seat, AF_KICKED)) != 0     { revert(); }
 
-    let amount = get_value();
     if amount == 0 { revert(); }
 
     let count = load_u8(&key_table(tid, TF_BETTOR_COUNT));
@@ -399,18 +1059,94 @@ fn handle_place_bet() -> ! {
     store_u8  (&key_bet(tid, count, BF_SEAT),   seat);
     store_u128(&key_bet(tid, count, BF_AMOUNT), amount);
     store_u8  (&key_bet(tid, count, BF_CLAIMED),0);
+    store_u8  (&key_bet(tid, count, BF_CANCELLED),0);
     store_u8  (&key_table(tid, TF_BETTOR_COUNT), count + 1);
 
     let abt = key_agent_bet_total(tid, seat);
     store_u128(&abt, load_u128(&abt) + amount);
+    let bt = key_bettor_total(tid, &caller);
+    store_u128(&bt, load_u128(&bt) + amount);
 
     let tbp = key_table(tid, TF_TOTAL_BET_POOL);
     store_u128(&tbp, load_u128(&tbp) + amount);
 
     emit(&BET_PLACED_TOPIC, &[seat]);
+    return_u8(count);
+}
+
+/// Bettor withdraws one of their own bets while betting is still open.
+fn handle_cancel_bet() -> ! {
+    let tid = read_u32_param(4);
+    let idx = read_u8_param(36);
+
+    if load_u8(&key_table(tid, TF_STATE)) != STATE_OPEN   { revert(); }
+    if get_now() >= load_u64(&key_table(tid, TF_BET_DEADLINE)) { revert(); }
+    if idx >= load_u8(&key_table(tid, TF_BETTOR_COUNT))    { revert(); }
+
+    let caller = get_caller();
+    if !addr_eq(&load_addr(&key_bet(tid, idx, BF_ADDR)), &caller) { revert(); }
+    if !bet_is_live(tid, idx) { revert(); }
+
+    cancel_bet_record(tid, idx);
+    return_empty();
+}
+
+/// Bettor pulls every live bet they hold on the table in one call, without
+/// having to know the bet indices. Same window as cancelBet.
+fn handle_withdraw_bet() -> ! {
+    let tid = read_u32_param(4);
+
+    if load_u8(&key_table(tid, TF_STATE)) != STATE_OPEN   { revert(); }
+    if get_now() >= load_u64(&key_table(tid, TF_BET_DEADLINE)) { revert(); }
+
+    let caller = get_caller();
+    let count = load_u8(&key_table(tid, TF_BETTOR_COUNT));
+    let mut found = false;
+    let mut i: u8 = 0;
+    while i < count {
+        if addr_eq(&load_addr(&key_bet(tid, i, BF_ADDR)), &caller) && bet_is_live(tid, i) {
+            cancel_bet_record(tid, i);
+            found = true;
+        }
+        i += 1;
+    }
+    if !found { revert(); }
     return_empty();
 }
 
+/// Mark a live bet cancelled, back it out of every running total and refund
+/// the bettor in full. Shared by cancelBet, withdrawBet and seat
+/// vacating.
+fn cancel_bet_record(tid: u32, idx: u8) {
+    let bettor = load_addr(&key_bet(tid, idx, BF_ADDR));
+    let seat   = load_u8(&key_bet(tid, idx, BF_SEAT));
+    let amount = load_u128(&key_bet(tid, idx, BF_AMOUNT));
+    store_u8(&key_bet(tid, idx, BF_CANCELLED), 1);
+
+    let abt = key_agent_bet_total(tid, seat);
+    store_u128(&abt, load_u128(&abt) - amount);
+    let bt = key_bettor_total(tid, &bettor);
+    store_u128(&bt, load_u128(&bt) - amount);
+    let tbp = key_table(tid, TF_TOTAL_BET_POOL);
+    store_u128(&tbp, load_u128(&tbp) - amount);
+
+    pay_out(tid, &bettor, amount);
+    emit(&BET_CANCELLED_TOPIC, &[seat]);
+}
+
+/// Refund every live bet backing `seat`. Must run before a seat is vacated
+/// (or its index reused) so no bet can settle against a different agent.
+fn refund_seat_bets(tid: u32, seat: u8) {
+    let count = load_u8(&key_table(tid, TF_BETTOR_COUNT));
+    let mut i: u8 = 0;
+    while i < count {
+        if load_u8(&key_bet(tid, i, BF_SEAT)) == seat && bet_is_live(tid, i) {
+            cancel_bet_record(tid, i);
+        }
+        i += 1;
+    }
+}
+
 // ============================================================================
 // DEALER ACTIONS
 // ============================================================================
@@ -424,41 +1160,150 @@ fn handle_deal() -> ! {
     let caller = get_caller();
     require_dealer(&caller);
 
-    let state = load_u8(&key_table(tid, TF_STATE));
-    let agent_count = load_u8(&key_table(tid, TF_AGENT_COUNT));
-    if agent_count < 2 { revert(); }
+    let (first, active, hand) = match deal_table(tid) {
+        Some(d) => d,
+        None => revert(),
+    };
 
-    if state == STATE_OPEN {
-        store_u8(&key_table(tid, TF_STATE), STATE_PLAYING);
-    } else if state != STATE_PLAYING {
-        revert();
-    }
+    // ABI: (uint8 firstToAct, uint8 activeCount, uint32 handNumber)
+    let mut r = [0u8; 96];
+    r[31] = first;
+    r[63] = active;
+    r[92..96].copy_from_slice(&hand.to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
 
-    let hand = load_u32(&key_table(tid, TF_CURRENT_HAND));
-    store_u32(&key_table(tid, TF_CURRENT_HAND), hand + 1);
+/// Start the next hand on `tid`. Every precondition is checked before the
+/// first write, so `None` leaves the table untouched (batchDeal skips it).
+/// Returns (firstToAct, activeCount, handNumber).
+fn deal_table(tid: u32) -> Option<(u8, u8, u32)> {
+    let state = load_u8(&key_table(tid, TF_STATE));
+    if state != STATE_OPEN && state != STATE_PLAYING { return None; }
+    let agent_count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    if agent_count < 2 { return None; }
+    if budget_spent(tid, get_now()) { return None; }
 
-    // Reset per-hand state for all non-kicked agents
     let mut active: u8 = 0;
     let mut first: u8 = 0;
-    let mut found = false;
     let mut i: u8 = 0;
     while i < agent_count {
         if load_u8(&key_agent(tid, i, AF_KICKED)) == 0 {
-            store_u8  (&key_agent(tid, i, AF_FOLDED),   0);
-            store_u128(&key_agent(tid, i, AF_HAND_BET), 0);
+            if active == 0 { first = i; }
             active += 1;
-            if !found { first = i; found = true; }
         }
         i += 1;
     }
-    if active < 2 { revert(); }
+    if active < 2 { return None; }
+
+    if state == STATE_OPEN {
+        store_u8(&key_table(tid, TF_STATE), STATE_PLAYING);
+        store_u64(&key_table(tid, TF_STARTED_AT), get_now());
+    }
+    clear_last_award(tid);
+
+    let hand = load_u32(&key_table(tid, TF_CURRENT_HAND)) + 1;
+    store_u32(&key_table(tid, TF_CURRENT_HAND), hand);
+
+    // Reset per-hand state for all non-kicked agents
+    let mut j: u8 = 0;
+    while j < agent_count {
+        let dealt = load_u8(&key_agent(tid, j, AF_KICKED)) == 0;
+        if dealt {
+            store_u8  (&key_agent(tid, j, AF_FOLDED),   0);
+            store_u128(&key_agent(tid, j, AF_HAND_BET), 0);
+        }
+        store_u8(&key_agent(tid, j, AF_DEALT_IN), dealt as u8);
+        store_u8(&key_agent(tid, j, AF_ACTED),    0);
+        store_u8(&key_agent(tid, j, AF_SINCE_RAISE),  0);
+        store_u8(&key_agent(tid, j, AF_RAISE_LOCKED), 0);
+        store_u128(&key_agent(tid, j, AF_ANTE_PAID),  0);
+        j += 1;
+    }
 
-    store_u128(&key_table(tid, TF_POT),          0);
-    store_u8  (&key_table(tid, TF_CURRENT_TURN), first);
-    store_u128(&key_table(tid, TF_CURRENT_BET),  0);
+    // Button moves one live seat left each hand (first live seat on hand 1).
+    // Heads-up the button posts the small blind and acts first preflop;
+    // otherwise the seat left of the big blind opens.
+    let button = if hand == 1 { first }
+        else { find_next_active(tid, load_u8(&key_table(tid, TF_BUTTON)), agent_count) };
+    let sb_seat = if active == 2 { button } else { find_next_active(tid, button, agent_count) };
+    let bb_seat = find_next_active(tid, sb_seat, agent_count);
+    let to_act  = if active == 2 { sb_seat } else { find_next_active(tid, bb_seat, agent_count) };
+
+    let antes = collect_antes(tid, agent_count, hand);
+    let sb = post_blind(tid, sb_seat, load_u128(&key_table(tid, TF_SMALL_BLIND)));
+    let bb = post_blind(tid, bb_seat, load_u128(&key_table(tid, TF_BIG_BLIND)));
+
+    store_u8  (&key_table(tid, TF_BUTTON),       button);
+    store_u128(&key_table(tid, TF_POT),          antes + sb + bb);
+    set_turn(tid, to_act);
+    store_u128(&key_table(tid, TF_CURRENT_BET),  sb.max(bb));
+    store_u128(&key_table(tid, TF_LAST_RAISE),   load_u128(&key_table(tid, TF_BIG_BLIND)));
     store_u8  (&key_table(tid, TF_ACTIVE_COUNT), active);
     touch_last_action(tid);
-    return_empty();
+    Some((to_act, active, hand))
+}
+
+/// Take the ante from every dealt-in seat (a short stack goes all-in for
+/// what it has). Antes are dead money: they go to the pot but not into
+/// AF_HAND_BET, so they don't count toward calling. Returns the total.
+/// ANTES_COLLECTED data: tableId(4) | hand(4) | total (u128).
+fn collect_antes(tid: u32, count: u8, hand: u32) -> u128 {
+    let ante = load_u128(&key_table(tid, TF_ANTE));
+    if ante == 0 { return 0; }
+    let mut total: u128 = 0;
+    let mut i: u8 = 0;
+    while i < count {
+        if load_u8(&key_agent(tid, i, AF_DEALT_IN)) != 0 {
+            let chips = load_u128(&key_agent(tid, i, AF_CHIPS));
+            let posted = ante.min(chips);
+            store_u128(&key_agent(tid, i, AF_CHIPS), chips - posted);
+            store_u128(&key_agent(tid, i, AF_ANTE_PAID), posted);
+            total += posted;
+        }
+        i += 1;
+    }
+    let mut data = [0u8; 24];
+    data[0..4].copy_from_slice(&tid.to_be_bytes());
+    data[4..8].copy_from_slice(&hand.to_be_bytes());
+    data[8..24].copy_from_slice(&total.to_be_bytes());
+    emit(&ANTES_COLLECTED_TOPIC, &data);
+    total
+}
+
+/// Move a forced bet from `seat`'s stack into its hand bet. A short stack
+/// posts what it has (all-in). Returns the amount posted; the caller adds
+/// it to the pot.
+fn post_blind(tid: u32, seat: u8, blind: u128) -> u128 {
+    let chips = load_u128(&key_agent(tid, seat, AF_CHIPS));
+    let posted = blind.min(chips);
+    store_u128(&key_agent(tid, seat, AF_CHIPS),    chips - posted);
+    store_u128(&key_agent(tid, seat, AF_HAND_BET), posted);
+    posted
+}
+
+/// Deal the next hand on up to MAX_BATCH tables in one call. With
+/// `skipFailures` a table that can't be dealt is left as-is; otherwise the
+/// whole batch reverts. Returns a uint32 bitmask of the tables dealt.
+fn handle_batch_deal() -> ! {
+    let caller = get_caller();
+    require_dealer(&caller);
+
+    let (start, len) = read_array_param(4);
+    let skip = read_u8_param(36) != 0;
+    if len == 0 || len > MAX_BATCH { revert(); }
+
+    let mut mask: u32 = 0;
+    let mut i: u32 = 0;
+    while i < len {
+        let tid = read_u32_param(start + 32 * i);
+        if deal_table(tid).is_some() {
+            mask |= 1 << i;
+        } else if !skip {
+            revert();
+        }
+        i += 1;
+    }
+    return_u32(mask);
 }
 
 fn handle_action() -> ! {
@@ -477,6 +1322,11 @@ fn handle_action() -> ! {
     let is_dealer = addr_eq(&caller, &dealer);
     let is_agent  = addr_eq(&caller, &agent_addr);
     if !is_dealer && !is_agent { revert(); }
+    // A seat already out of the hand can't act (or be timed out) again
+    if load_u8(&key_agent(tid, turn, AF_FOLDED)) != 0 { revert(); }
+    if load_u8(&key_agent(tid, turn, AF_KICKED)) != 0 { revert(); }
+    clear_last_award(tid);
+    store_u8(&key_agent(tid, turn, AF_ACTED), 1);
 
     let chips       = load_u128(&key_agent(tid, turn, AF_CHIPS));
     let cur_bet     = load_u128(&key_table(tid, TF_CURRENT_BET));
@@ -486,27 +1336,8 @@ fn handle_action() -> ! {
 
     match action {
         ACT_FOLD => {
-            store_u8(&key_agent(tid, turn, AF_FOLDED), 1);
-            let ac = load_u8(&key_table(tid, TF_ACTIVE_COUNT)) - 1;
-            store_u8(&key_table(tid, TF_ACTIVE_COUNT), ac);
-
             // Dealer-submitted fold = timeout → track misses
-            if is_dealer {
-                let missed = load_u8(&key_agent(tid, turn, AF_MISSED)) + 1;
-                store_u8(&key_agent(tid, turn, AF_MISSED), missed);
-                if missed >= MISSED_TURNS_KICK {
-                    kick_agent(tid, turn, &agent_addr, agent_count);
-                    touch_last_action(tid);
-                    return_empty();
-                }
-            }
-
-            if ac == 1 {
-                let winner = find_active(tid, agent_count);
-                award_pot(tid, winner, agent_count);
-                touch_last_action(tid);
-                return_empty();
-            }
+            if fold_seat(tid, turn, &agent_addr, agent_count, is_dealer) { return_empty(); }
         }
         ACT_CHECK => {
             if to_call != 0 { revert(); }
@@ -520,10 +1351,23 @@ fn handle_action() -> ! {
             store_u128(&key_table(tid, TF_POT), pot + to_call);
         }
         ACT_RAISE => {
-            if amount == 0 { revert(); }
-            let total = to_call + amount;
-            if chips < total { revert(); }
-            let new_hand_bet = agent_bet + total;
+            // `amount` is caller-supplied: bound it by the stack before any
+            // arithmetic so an absurd raise reverts here, not in a trap
+            if amount == 0 || amount > chips { revert(); }
+            let total = match to_call.checked_add(amount) {
+                Some(t) if t <= chips => t,
+                _ => revert(),
+            };
+            // At least the last raise (BB floor), except all-in for less —
+            // which doesn't reopen betting for seats that already acted
+            if load_u8(&key_agent(tid, turn, AF_RAISE_LOCKED)) != 0 { revert(); }
+            let full = amount >= load_u128(&key_table(tid, TF_LAST_RAISE));
+            if !full && total != chips { revert(); }
+            mark_raise(tid, turn, agent_count, full, amount);
+            let new_hand_bet = match agent_bet.checked_add(total) {
+                Some(b) => b,
+                None => revert(),
+            };
             store_u128(&key_agent(tid, turn, AF_CHIPS),   chips - total);
             store_u128(&key_agent(tid, turn, AF_HAND_BET), new_hand_bet);
             store_u128(&key_table(tid, TF_CURRENT_BET),   new_hand_bet);
@@ -533,68 +1377,356 @@ fn handle_action() -> ! {
         _ => revert(),
     }
 
-    // Voluntary action: reset missed turns
+    // Voluntary action breaks the timeout streak
     if is_agent {
-        store_u8(&key_agent(tid, turn, AF_MISSED), 0);
+        clear_misses(tid, turn);
+    }
+    end_turn(tid, turn, agent_count);
+    return_empty();
+}
+
+/// Fold `turn`. A timeout fold counts a miss and kicks at the threshold;
+/// the last seat standing wins the pot. Returns true when the hand is over
+/// for this call (walk, or a kick that ended the session) and the turn
+/// must not advance.
+fn fold_seat(tid: u32, turn: u8, addr: &[u8;20], agent_count: u8, timed_out: bool) -> bool {
+    store_u8(&key_agent(tid, turn, AF_FOLDED), 1);
+    let ac = load_u8(&key_table(tid, TF_ACTIVE_COUNT)) - 1;
+    store_u8(&key_table(tid, TF_ACTIVE_COUNT), ac);
+
+    if timed_out {
+        let missed = record_miss(tid, turn);
+        if missed >= table_kick_threshold(tid) {
+            kick_agent(tid, turn, addr, agent_count);
+            // Otherwise play goes on past the kicked seat like any fold
+            if load_u8(&key_table(tid, TF_STATE)) != STATE_PLAYING {
+                touch_last_action(tid);
+                return true;
+            }
+        }
     }
 
-    // Advance turn to next active agent
-    let next = find_next_active(tid, turn, agent_count);
-    store_u8(&key_table(tid, TF_CURRENT_TURN), next);
+    if ac == 1 {
+        let winner = find_active(tid, agent_count);
+        award_pot(tid, winner, agent_count, RESOLVE_FOLD_WIN);
+        touch_last_action(tid);
+        return true;
+    }
+    false
+}
+
+/// Close `turn`'s action and pass it to the next active agent
+fn end_turn(tid: u32, turn: u8, agent_count: u8) {
+    store_u8(&key_agent(tid, turn, AF_SINCE_RAISE),  1);
+    store_u8(&key_agent(tid, turn, AF_RAISE_LOCKED), 0);
+    set_turn(tid, find_next_active(tid, turn, agent_count));
     touch_last_action(tid);
-    return_empty();
 }
 
-fn handle_resolve_hand() -> ! {
-    let tid    = read_u32_param(4);
-    let winner = read_u8_param(36);
+/// Hand the action to `seat` and start its clock (if the table has one)
+fn set_turn(tid: u32, seat: u8) {
+    store_u8(&key_table(tid, TF_CURRENT_TURN), seat);
+    let secs = load_u64(&key_table(tid, TF_TURN_TIMEOUT));
+    let deadline = if secs == 0 { 0 } else { get_now() + secs };
+    store_u64(&key_table(tid, TF_TURN_DEADLINE), deadline);
+}
 
+/// Dealer sets how long each agent gets to act on this table; 0 turns the
+/// clock off (timeouts are then dealer-submitted only). Takes effect from
+/// the next turn.
+fn handle_set_turn_timeout() -> ! {
+    let tid  = read_u32_param(4);
+    let secs = read_u64_param(36);
     let caller = get_caller();
     require_dealer(&caller);
+    let state = load_u8(&key_table(tid, TF_STATE));
+    if state != STATE_OPEN && state != STATE_PLAYING { revert(); }
+    store_u64(&key_table(tid, TF_TURN_TIMEOUT), secs);
+    return_empty();
+}
+
+/// Permissionless timeout: once the current turn's deadline has passed,
+/// anyone can fold the agent to act. Counts as a miss exactly like a
+/// dealer-submitted timeout fold.
+fn handle_force_fold() -> ! {
+    let tid = read_u32_param(4);
     if load_u8(&key_table(tid, TF_STATE)) != STATE_PLAYING { revert(); }
+    let deadline = load_u64(&key_table(tid, TF_TURN_DEADLINE));
+    if deadline == 0 || get_now() <= deadline { revert(); }
 
+    let turn = load_u8(&key_table(tid, TF_CURRENT_TURN));
+    if load_u8(&key_agent(tid, turn, AF_FOLDED)) != 0 { revert(); }
+    if load_u8(&key_agent(tid, turn, AF_KICKED)) != 0 { revert(); }
+    let agent_addr  = load_addr(&key_agent(tid, turn, AF_ADDR));
     let agent_count = load_u8(&key_table(tid, TF_AGENT_COUNT));
-    if winner >= agent_count { revert(); }
-    if load_u8(&key_agent(tid, winner, AF_KICKED)) != 0 { revert(); }
-    if load_u8(&key_agent(tid, winner, AF_FOLDED)) != 0 { revert(); }
 
-    award_pot(tid, winner, agent_count);
-    touch_last_action(tid);
+    clear_last_award(tid);
+    store_u8(&key_agent(tid, turn, AF_ACTED), 1);
+    if fold_seat(tid, turn, &agent_addr, agent_count, true) { return_empty(); }
+    end_turn(tid, turn, agent_count);
     return_empty();
 }
 
-fn handle_end_session() -> ! {
+/// A full raise sets the new minimum and reopens betting for everyone; a
+/// short all-in locks the seats that already acted since the last full
+/// raise to call/fold until they act again.
+fn mark_raise(tid: u32, raiser: u8, count: u8, full: bool, amount: u128) {
+    if full { store_u128(&key_table(tid, TF_LAST_RAISE), amount); }
+    let mut i: u8 = 0;
+    while i < count {
+        if i != raiser {
+            if full {
+                store_u8(&key_agent(tid, i, AF_SINCE_RAISE),  0);
+                store_u8(&key_agent(tid, i, AF_RAISE_LOCKED), 0);
+            } else if load_u8(&key_agent(tid, i, AF_SINCE_RAISE)) != 0 {
+                store_u8(&key_agent(tid, i, AF_RAISE_LOCKED), 1);
+            }
+        }
+        i += 1;
+    }
+}
+
+/// The betting round is closed when every seat still in the hand with
+/// chips behind has acted since the last full raise and matched the
+/// current bet. All-in seats have nothing left to decide.
+fn round_complete(tid: u32) -> bool {
+    if load_u8(&key_table(tid, TF_STATE)) != STATE_PLAYING { return false; }
+    let count   = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let cur_bet = load_u128(&key_table(tid, TF_CURRENT_BET));
+    let mut i: u8 = 0;
+    while i < count {
+        if load_u8(&key_agent(tid, i, AF_DEALT_IN)) != 0 &&
+           load_u8(&key_agent(tid, i, AF_FOLDED)) == 0 &&
+           load_u8(&key_agent(tid, i, AF_KICKED)) == 0 &&
+           load_u128(&key_agent(tid, i, AF_CHIPS)) > 0 {
+            if load_u8(&key_agent(tid, i, AF_SINCE_RAISE)) == 0 { return false; }
+            if load_u128(&key_agent(tid, i, AF_HAND_BET)) != cur_bet { return false; }
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Dealer opens the next street once the current round is closed: everyone
+/// gets to act again, the min raise drops back to the big blind and action
+/// starts left of the button. Hand bets stay cumulative, so checking
+/// around keeps to_call at 0.
+fn handle_advance_round() -> ! {
     let tid = read_u32_param(4);
     let caller = get_caller();
     require_dealer(&caller);
+    if !round_complete(tid) { revert(); }
 
-    let state = load_u8(&key_table(tid, TF_STATE));
-    if state == STATE_OPEN {
-        store_u8(&key_table(tid, TF_STATE), STATE_CANCELLED);
-    } else if state == STATE_PLAYING {
-        store_u8(&key_table(tid, TF_STATE), STATE_ENDED);
-    } else {
-        revert();
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let mut i: u8 = 0;
+    while i < count {
+        store_u8(&key_agent(tid, i, AF_SINCE_RAISE),  0);
+        store_u8(&key_agent(tid, i, AF_RAISE_LOCKED), 0);
+        i += 1;
     }
-    emit(&SESSION_ENDED_TOPIC, &tid.to_be_bytes());
+    store_u128(&key_table(tid, TF_LAST_RAISE), load_u128(&key_table(tid, TF_BIG_BLIND)));
+    let button = load_u8(&key_table(tid, TF_BUTTON));
+    set_turn(tid, find_next_active(tid, button, count));
+    clear_last_award(tid);
+    touch_last_action(tid);
     return_empty();
 }
 
-fn handle_cancel() -> ! {
-    let tid = read_u32_param(4);
-    let state = load_u8(&key_table(tid, TF_STATE));
-    let now = get_now();
+fn handle_resolve_hand() -> ! {
+    let tid    = read_u32_param(4);
+    let winner = read_u8_param(36);
+
+    let caller = get_caller();
+    require_dealer(&caller);
+    if !resolve_table(tid, winner) { revert(); }
+    return_empty();
+}
+
+/// Award the pot on `tid` to `winner` after a showdown. Returns false,
+/// without writing anything, if the table or seat isn't eligible or the
+/// betting isn't closed — a seat that still owes a call can't be resolved
+/// past.
+fn resolve_table(tid: u32, winner: u8) -> bool {
+    if load_u8(&key_table(tid, TF_STATE)) != STATE_PLAYING { return false; }
+    if !round_complete(tid) { return false; }
+
+    let agent_count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    if winner >= agent_count { return false; }
+    if load_u8(&key_agent(tid, winner, AF_KICKED)) != 0 { return false; }
+    if load_u8(&key_agent(tid, winner, AF_FOLDED)) != 0 { return false; }
+
+    let pot = load_u128(&key_table(tid, TF_POT));
+    award_pot(tid, winner, agent_count, RESOLVE_DEALER);
+    touch_last_action(tid);
+
+    // Remember the award so a fat-fingered winner can be corrected
+    store_u128(&key_table(tid, TF_LAST_AWARD),      pot);
+    store_u8  (&key_table(tid, TF_LAST_AWARD_SEAT), winner);
+    store_u64 (&key_table(tid, TF_LAST_AWARD_AT),   get_now());
+    true
+}
+
+/// Split the pot between tied winners, given as a seat bitmask. Each gets
+/// pot / n; the remainder goes to the lowest winning seat. Emits
+/// HAND_RESOLVED (RESOLVE_SPLIT) once per winner. A split can't be
+/// disputed — disputeResolve only moves a single-seat award.
+fn handle_resolve_hand_multi() -> ! {
+    let tid  = read_u32_param(4);
+    let mask = read_u8_param(36);
+
+    let caller = get_caller();
+    require_dealer(&caller);
+    if load_u8(&key_table(tid, TF_STATE)) != STATE_PLAYING { revert(); }
+    if !round_complete(tid) { revert(); }
+
+    let agent_count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    if mask == 0 || (agent_count < 8 && mask >> agent_count != 0) { revert(); }
+    let mut n: u8 = 0;
+    let mut i: u8 = 0;
+    while i < agent_count {
+        if mask & (1 << i) != 0 {
+            if load_u8(&key_agent(tid, i, AF_KICKED)) != 0 { revert(); }
+            if load_u8(&key_agent(tid, i, AF_FOLDED)) != 0 { revert(); }
+            n += 1;
+        }
+        i += 1;
+    }
+
+    pay_pot(tid, mask, n, agent_count, RESOLVE_SPLIT);
+    clear_last_award(tid);
+    touch_last_action(tid);
+    return_empty();
+}
+
+/// Nothing can be disputed once play has moved on.
+fn clear_last_award(tid: u32) {
+    store_u64(&key_table(tid, TF_LAST_AWARD_AT), 0);
+}
+
+/// Dealer safety valve: move the last resolved pot from the seat it went to
+/// onto the correct winner. Only before the next deal/action, within
+/// DISPUTE_WINDOW_SECS, and — if the resolve ended the session — only while
+/// no payout that depends on the chip standings has gone out.
+fn handle_dispute_resolve() -> ! {
+    let tid     = read_u32_param(4);
+    let correct = read_u8_param(36);
+
+    let caller = get_caller();
+    require_dealer(&caller);
+
+    let at = load_u64(&key_table(tid, TF_LAST_AWARD_AT));
+    if at == 0 || get_now().saturating_sub(at) > DISPUTE_WINDOW_SECS { revert(); }
+
+    let state = load_u8(&key_table(tid, TF_STATE));
+    if state != STATE_PLAYING && state != STATE_ENDED { revert(); }
+
+    let wrong = load_u8(&key_table(tid, TF_LAST_AWARD_SEAT));
+    let agent_count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    if correct >= agent_count || correct == wrong { revert(); }
+    if load_u8(&key_agent(tid, correct, AF_KICKED)) != 0 { revert(); }
+    if load_u8(&key_agent(tid, correct, AF_FOLDED)) != 0 { revert(); }
+
+    if state == STATE_ENDED {
+        if load_u8(&key_table(tid, TF_PRIZE_FEE_PAID)) != 0 { revert(); }
+        if load_u8(&key_table(tid, TF_BET_FEE_PAID)) != 0   { revert(); }
+        if load_u8(&key_agent(tid, wrong,   AF_CHIPS_CLAIMED)) != 0 { revert(); }
+        if load_u8(&key_agent(tid, correct, AF_CHIPS_CLAIMED)) != 0 { revert(); }
+    }
+
+    let amount = load_u128(&key_table(tid, TF_LAST_AWARD));
+    let wrong_chips = load_u128(&key_agent(tid, wrong, AF_CHIPS));
+    if wrong_chips < amount { revert(); }
+    store_u128(&key_agent(tid, wrong, AF_CHIPS), wrong_chips - amount);
+    let correct_chips = load_u128(&key_agent(tid, correct, AF_CHIPS));
+    store_u128(&key_agent(tid, correct, AF_CHIPS), correct_chips + amount);
+    clear_last_award(tid);
+
+    emit_hand_resolved(tid, correct, RESOLVE_CORRECTED, amount);
+    return_empty();
+}
+
+/// Resolve hands on up to MAX_BATCH tables: tableIds[i] is won by
+/// winners[i]. Same skip/revert semantics and return mask as batchDeal.
+fn handle_batch_resolve() -> ! {
+    let caller = get_caller();
+    require_dealer(&caller);
+
+    let (tids, len)     = read_array_param(4);
+    let (seats, n_seat) = read_array_param(36);
+    let skip = read_u8_param(68) != 0;
+    if len == 0 || len > MAX_BATCH || n_seat != len { revert(); }
+
+    let mut mask: u32 = 0;
+    let mut i: u32 = 0;
+    while i < len {
+        let tid    = read_u32_param(tids + 32 * i);
+        let winner = read_u8_param(seats + 32 * i);
+        if resolve_table(tid, winner) {
+            mask |= 1 << i;
+        } else if !skip {
+            revert();
+        }
+        i += 1;
+    }
+    return_u32(mask);
+}
+
+/// Dealer liveness ping between hands. Refreshes the inactivity timer without
+/// touching game state, but only within the table's time budget — past that
+/// no new hand can be dealt either, so the table ends or gets cancelled.
+fn handle_heartbeat() -> ! {
+    let tid = read_u32_param(4);
+    let caller = get_caller();
+    require_dealer(&caller);
+    if load_u8(&key_table(tid, TF_STATE)) != STATE_PLAYING { revert(); }
+
+    let now = get_now();
+    if budget_spent(tid, now) { revert(); }
+
+    touch_last_action(tid);
+
+    let mut data = [0u8; 12];
+    data[0..4].copy_from_slice(&tid.to_be_bytes());
+    data[4..12].copy_from_slice(&now.to_be_bytes());
+    emit(&HEARTBEAT_TOPIC, &data);
+    return_empty();
+}
+
+fn handle_end_session() -> ! {
+    let tid = read_u32_param(4);
+    let caller = get_caller();
+    require_dealer(&caller);
+
+    let state = load_u8(&key_table(tid, TF_STATE));
+    if state == STATE_OPEN {
+        store_u8(&key_table(tid, TF_STATE), STATE_CANCELLED);
+    } else if state == STATE_PLAYING {
+        store_u8(&key_table(tid, TF_STATE), STATE_ENDED);
+    } else {
+        revert();
+    }
+    emit(&SESSION_ENDED_TOPIC, &tid.to_be_bytes());
+    return_empty();
+}
+
+fn handle_cancel() -> ! {
+    let tid = read_u32_param(4);
+    let state = load_u8(&key_table(tid, TF_STATE));
+    let now = get_now();
 
     let can_cancel = match state {
         STATE_OPEN => {
             // Betting window closed and dealer never dealt
-            now > load_u64(&key_table(tid, TF_BET_DEADLINE)) &&
-            load_u32(&key_table(tid, TF_CURRENT_HAND)) == 0
+            (now > load_u64(&key_table(tid, TF_BET_DEADLINE)) &&
+             load_u32(&key_table(tid, TF_CURRENT_HAND)) == 0) ||
+            // Creator pulls an empty table early (no agents → no bets either)
+            (load_u8(&key_table(tid, TF_AGENT_COUNT)) == 0 &&
+             addr_eq(&get_caller(), &load_addr(&key_table(tid, TF_CREATOR))))
         }
         STATE_PLAYING => {
-            // Dealer has gone dark for over 1 hour
+            // Dealer has gone dark for longer than the inactivity window
             let last = load_u64(&key_table(tid, TF_LAST_ACTION));
-            now.saturating_sub(last) > INACTIVITY_SECS
+            now.saturating_sub(last) > table_inactivity_secs(tid)
         }
         _ => false,
     };
@@ -615,19 +1747,87 @@ fn handle_update_dealer() -> ! {
     return_empty();
 }
 
+/// Platform override for the inactivity window and kick threshold.
+/// Applies to every table; pass 0 to fall back to the compiled default.
+/// A nonzero window has the same floor and ceiling as createTable.
+fn handle_set_global_thresholds() -> ! {
+    let caller = get_caller();
+    let platform = load_addr(&key_platform());
+    if !addr_eq(&caller, &platform) { revert(); }
+
+    let inactivity = read_u64_param(4);
+    let kick       = read_u8_param(36);
+    if inactivity != 0 &&
+       (inactivity < MIN_INACTIVITY_SECS || inactivity > MAX_INACTIVITY_SECS) { revert(); }
+    store_u64(&key_inactivity_override(), inactivity);
+    store_u8 (&key_kick_override(),       kick);
+    return_empty();
+}
+
+/// Platform sets the fee for tables created from now on; existing tables
+/// keep the rate they were created with. Capped at MAX_PLATFORM_FEE_BPS.
+fn handle_update_fee() -> ! {
+    let caller = get_caller();
+    let platform = load_addr(&key_platform());
+    if !addr_eq(&caller, &platform) { revert(); }
+
+    let bps = read_u32_param(4);
+    if bps > MAX_PLATFORM_FEE_BPS { revert(); }
+    store_u32(&key_platform_fee(), bps);
+    return_empty();
+}
+
+/// Platform sets the buy-in band for tables created from now on.
+fn handle_set_buy_in_bounds() -> ! {
+    let caller = get_caller();
+    let platform = load_addr(&key_platform());
+    if !addr_eq(&caller, &platform) { revert(); }
+
+    let min = read_u256_as_u128(4);
+    let max = read_u256_as_u128(36);
+    if min == 0 || min > max || max > DEFAULT_MAX_BUY_IN { revert(); }
+    store_u128(&key_min_buy_in(), min);
+    store_u128(&key_max_buy_in(), max);
+    return_empty();
+}
+
 // ============================================================================
 // INTERNAL GAME LOGIC
 // ============================================================================
 
-/// Kick an agent: mark kicked, forfeit remaining chips to prize pool
-fn kick_agent(tid: u32, seat: u8, addr: &[u8;20], _agent_count: u8) {
+// Kick semantics: AF_MISSED counts CONSECUTIVE timed-out turns. The streak
+// carries across hands (deal_table never touches it) and is broken only by
+// the agent acting on its own turn. So miss, act, miss, miss is a streak of
+// 2, not 3 — only kick_threshold() timeouts in a row kick.
+
+/// Dealer-submitted timeout fold: extend the seat's streak, return it.
+fn record_miss(tid: u32, seat: u8) -> u8 {
+    let missed = load_u8(&key_agent(tid, seat, AF_MISSED)).saturating_add(1);
+    store_u8(&key_agent(tid, seat, AF_MISSED), missed);
+    missed
+}
+
+/// Agent acted on its own turn: the streak starts over.
+fn clear_misses(tid: u32, seat: u8) {
+    store_u8(&key_agent(tid, seat, AF_MISSED), 0);
+}
+
+/// Kick an agent: mark kicked, forfeit remaining chips per the table's
+/// forfeit policy. A seat that is already kicked is left alone, so the
+/// alive count drops once per agent.
+fn kick_agent(tid: u32, seat: u8, addr: &[u8;20], agent_count: u8) {
+    if load_u8(&key_agent(tid, seat, AF_KICKED)) != 0 { return; }
     store_u8(&key_agent(tid, seat, AF_KICKED), 1);
+    let alive = load_u8(&key_table(tid, TF_ALIVE_COUNT));
+    store_u8(&key_table(tid, TF_ALIVE_COUNT), alive - 1);
 
-    // Forfeit chips to prize pool
     let chips = load_u128(&key_agent(tid, seat, AF_CHIPS));
     store_u128(&key_agent(tid, seat, AF_CHIPS), 0);
-    let prize = load_u128(&key_table(tid, TF_PRIZE_POOL));
-    store_u128(&key_table(tid, TF_PRIZE_POOL), prize + chips);
+    match load_u8(&key_table(tid, TF_FORFEIT_POLICY)) {
+        FORFEIT_TO_AGENTS => forfeit_to_agents(tid, chips, agent_count),
+        FORFEIT_BURN => {}
+        _ => add_to_prize_pool(tid, chips),
+    }
 
     let mut data = [0u8; 21];
     data[0] = seat;
@@ -638,45 +1838,114 @@ fn kick_agent(tid: u32, seat: u8, addr: &[u8;20], _agent_count: u8) {
     maybe_end_session(tid);
 }
 
-/// Award pot to winner, check session end
-fn award_pot(tid: u32, winner: u8, agent_count: u8) {
-    let pot   = load_u128(&key_table(tid, TF_POT));
-    let chips = load_u128(&key_agent(tid, winner, AF_CHIPS));
-    store_u128(&key_agent(tid, winner, AF_CHIPS), chips + pot);
-    store_u128(&key_table(tid, TF_POT), 0);
+fn add_to_prize_pool(tid: u32, amount: u128) {
+    let prize = load_u128(&key_table(tid, TF_PRIZE_POOL));
+    store_u128(&key_table(tid, TF_PRIZE_POOL), prize + amount);
+}
 
-    let mut data = [0u8; 5];
-    data[0..4].copy_from_slice(&load_u32(&key_table(tid, TF_CURRENT_HAND)).to_be_bytes());
-    data[4] = winner;
-    emit(&HAND_RESOLVED_TOPIC, &data);
+/// Split forfeited chips across non-kicked agents in proportion to their
+/// stacks. Truncation dust — or everything, if no one left holds chips —
+/// goes to the prize pool so no value is stranded.
+fn forfeit_to_agents(tid: u32, amount: u128, agent_count: u8) {
+    let mut total: u128 = 0;
+    let mut i: u8 = 0;
+    while i < agent_count {
+        if load_u8(&key_agent(tid, i, AF_KICKED)) == 0 {
+            total += load_u128(&key_agent(tid, i, AF_CHIPS));
+        }
+        i += 1;
+    }
+    if total == 0 {
+        add_to_prize_pool(tid, amount);
+        return;
+    }
+
+    let mut paid: u128 = 0;
+    let mut j: u8 = 0;
+    while j < agent_count {
+        if load_u8(&key_agent(tid, j, AF_KICKED)) == 0 {
+            let c = load_u128(&key_agent(tid, j, AF_CHIPS));
+            let share = amount * c / total;
+            store_u128(&key_agent(tid, j, AF_CHIPS), c + share);
+            paid += share;
+        }
+        j += 1;
+    }
+    add_to_prize_pool(tid, amount - paid);
+}
+
+/// Award pot to winner, check session end. `kind` is one of RESOLVE_*.
+/// Reverts rather than pay a seat that can't win (NO_SEAT from find_active
+/// included) — the pot is never misattributed out of a corrupt state.
+fn award_pot(tid: u32, winner: u8, agent_count: u8, kind: u8) {
+    if winner >= agent_count ||
+       load_u8(&key_agent(tid, winner, AF_KICKED)) != 0 ||
+       load_u8(&key_agent(tid, winner, AF_FOLDED)) != 0 { revert(); }
+    pay_pot(tid, 1 << winner, 1, agent_count, kind);
+}
+
+/// Credit the pot to the `n` seats in `mask` (callers check they can win):
+/// pot / n each, the remainder to the lowest seat, one HAND_RESOLVED per
+/// seat. Then clear the betting state for the next hand and check whether
+/// the session is over.
+fn pay_pot(tid: u32, mask: u8, n: u8, agent_count: u8, kind: u8) {
+    let pot = load_u128(&key_table(tid, TF_POT));
+    let share = pot / (n as u128);
+    let mut remainder = pot % (n as u128);
+    let mut s: u8 = 0;
+    while s < agent_count {
+        if mask & (1 << s) != 0 {
+            let won = share + remainder;
+            remainder = 0;
+            let chips = load_u128(&key_agent(tid, s, AF_CHIPS));
+            store_u128(&key_agent(tid, s, AF_CHIPS), chips + won);
+            emit_hand_resolved(tid, s, kind, won);
+        }
+        s += 1;
+    }
+    store_u128(&key_table(tid, TF_POT), 0);
 
-    // Reset current bet for next hand
+    // Reset current bet for next hand; nobody is on the clock until it's dealt
     store_u128(&key_table(tid, TF_CURRENT_BET), 0);
+    store_u64 (&key_table(tid, TF_TURN_DEADLINE), 0);
 
-    // Count alive agents and check session end
-    let _ = agent_count; // used via maybe_end_session
     maybe_end_session(tid);
 }
 
+/// HAND_RESOLVED data: hand(4) | seat | kind | net won (u128) | amount
+/// (u128) | seat's chips after (u128). Net is the pot less the seat's own
+/// contribution this hand, so a walk (everyone folds to the last bettor)
+/// reports only the chips taken from others; amount is the gross sum moved
+/// to the seat. Callers credit the chips before emitting.
+fn emit_hand_resolved(tid: u32, seat: u8, kind: u8, pot: u128) {
+    let own = load_u128(&key_agent(tid, seat, AF_HAND_BET));
+    let mut data = [0u8; 54];
+    data[0..4].copy_from_slice(&load_u32(&key_table(tid, TF_CURRENT_HAND)).to_be_bytes());
+    data[4] = seat;
+    data[5] = kind;
+    data[6..22].copy_from_slice(&pot.saturating_sub(own).to_be_bytes());
+    data[22..38].copy_from_slice(&pot.to_be_bytes());
+    data[38..54].copy_from_slice(&load_u128(&key_agent(tid, seat, AF_CHIPS)).to_be_bytes());
+    emit(&HAND_RESOLVED_TOPIC, &data);
+}
+
+/// Below the table's min-hands floor, kicks alone never end the session —
+/// otherwise a dealer could kick everyone at hand 1 and crown a chosen
+/// winner. Alive only drops through kicks, so a table stuck under the floor
+/// can't deal again and falls through to cancel (B) and full refunds.
 fn maybe_end_session(tid: u32) {
     let hand    = load_u32(&key_table(tid, TF_CURRENT_HAND));
     let slen    = load_u32(&key_table(tid, TF_SESSION_LEN));
-    let acount  = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let alive   = load_u8(&key_table(tid, TF_ALIVE_COUNT));
+    let floor   = load_u32(&key_table(tid, TF_MIN_HANDS));
 
-    let mut alive: u8 = 0;
-    let mut i: u8 = 0;
-    while i < acount {
-        if load_u8(&key_agent(tid, i, AF_KICKED)) == 0 { alive += 1; }
-        i += 1;
-    }
-
-    if hand >= slen || alive <= 1 {
+    if hand >= slen || (alive <= 1 && hand >= floor) || budget_spent(tid, get_now()) {
         store_u8(&key_table(tid, TF_STATE), STATE_ENDED);
         emit(&SESSION_ENDED_TOPIC, &tid.to_be_bytes());
     }
 }
 
-/// Find the first non-kicked, non-folded agent
+/// Find the first non-kicked, non-folded agent, or NO_SEAT if none is
 fn find_active(tid: u32, count: u8) -> u8 {
     let mut i: u8 = 0;
     while i < count {
@@ -684,7 +1953,7 @@ fn find_active(tid: u32, count: u8) -> u8 {
            load_u8(&key_agent(tid, i, AF_FOLDED)) == 0 { return i; }
         i += 1;
     }
-    0
+    NO_SEAT
 }
 
 /// Find next active agent after `after` (wrapping)
@@ -713,6 +1982,27 @@ fn find_chip_leaders(tid: u32, count: u8) -> (u128, u8) {
     (max, num)
 }
 
+/// Seat's cut of the net prize pool under the table's TIE_* policy, 0 for
+/// a seat that isn't a chip leader (or loses the tie-break). EvenSplit hands
+/// the truncation remainder to the earliest tied seat, so the whole net
+/// pool is always paid out.
+fn prize_share(tid: u32, seat: u8) -> u128 {
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let (max_chips, num_winners) = find_chip_leaders(tid, count);
+    if max_chips == 0 || load_u128(&key_agent(tid, seat, AF_CHIPS)) != max_chips { return 0; }
+
+    let mut first: u8 = 0;
+    while load_u128(&key_agent(tid, first, AF_CHIPS)) != max_chips { first += 1; }
+    let earliest = seat == first;
+
+    let net = net_of_fees(tid, load_u128(&key_table(tid, TF_PRIZE_POOL)));
+    if load_u8(&key_table(tid, TF_TIE_POLICY)) == TIE_EARLIEST_SEAT {
+        return if earliest { net } else { 0 };
+    }
+    let n = num_winners as u128;
+    net / n + if earliest { net % n } else { 0 }
+}
+
 // ============================================================================
 // PAYOUTS
 // ============================================================================
@@ -730,7 +2020,8 @@ fn handle_claim_chips() -> ! {
             let chips = load_u128(&key_agent(tid, i, AF_CHIPS));
             if chips == 0 { revert(); }
             store_u8(&key_agent(tid, i, AF_CHIPS_CLAIMED), 1);
-            transfer_to(&caller, chips);
+            pay_out(tid, &caller, chips);
+            emit_claim(&CHIPS_CLAIMED_TOPIC, tid, &caller, chips);
             return_empty();
         }
         i += 1;
@@ -744,7 +2035,7 @@ fn handle_claim_prize() -> ! {
 
     let caller = get_caller();
     let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
-    let (max_chips, num_winners) = find_chip_leaders(tid, count);
+    let (max_chips, _) = find_chip_leaders(tid, count);
     if max_chips == 0 { revert(); }
 
     // Find caller's seat and verify they're a chip leader
@@ -764,20 +2055,66 @@ fn handle_claim_prize() -> ! {
     let prize_pool = load_u128(&key_table(tid, TF_PRIZE_POOL));
     if prize_pool == 0 { revert(); }
 
-    // Pay platform fee once (first winner to claim triggers it)
-    if load_u8(&key_table(tid, TF_PRIZE_FEE_PAID)) == 0 {
-        store_u8(&key_table(tid, TF_PRIZE_FEE_PAID), 1);
-        let fee = prize_pool * PLATFORM_FEE_BPS / 10_000;
-        let platform = load_addr(&key_platform());
-        transfer_to(&platform, fee);
-    }
-
-    // Caller gets 1/num_winners share of 95%
-    let net_pool = prize_pool - (prize_pool * PLATFORM_FEE_BPS / 10_000);
-    let payout = net_pool / (num_winners as u128);
+    // Caller's share of what's left after fees, per the tie policy
+    let payout = prize_share(tid, caller_seat);
+    if payout == 0 { revert(); }
 
+    // Effects before interactions: both flags are set before the first
+    // transfer, so a recipient reentering from the fee transfer finds this
+    // claim (and the fee) already spent.
+    let fee_due = load_u8(&key_table(tid, TF_PRIZE_FEE_PAID)) == 0;
+    store_u8(&key_table(tid, TF_PRIZE_FEE_PAID), 1);
     store_u8(&key_agent(tid, caller_seat, AF_PRIZE_CLAIMED), 1);
-    transfer_to(&caller, payout);
+
+    // Pay platform + creator fees once (first winner to claim triggers it)
+    if fee_due { pay_pool_fees(tid, prize_pool, FEE_POOL_PRIZE); }
+    pay_out(tid, &caller, payout);
+    emit_claim(&PRIZE_CLAIMED_TOPIC, tid, &caller, payout);
+    return_empty();
+}
+
+/// claimChips + claimPrize in one call: the caller's unclaimed stack plus,
+/// if they lead on chips, their prize share, paid as one transfer. Each part
+/// is skipped once claimed; reverts when nothing is left to pay.
+fn handle_claim_all() -> ! {
+    let tid = read_u32_param(4);
+    if load_u8(&key_table(tid, TF_STATE)) != STATE_ENDED { revert(); }
+
+    let caller = get_caller();
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let (max_chips, _) = find_chip_leaders(tid, count);
+
+    let seat = seat_of(tid, &caller);
+    if seat == NO_SEAT { revert(); }
+
+    let chips = load_u128(&key_agent(tid, seat, AF_CHIPS));
+    let mut chips_paid: u128 = 0;
+    if load_u8(&key_agent(tid, seat, AF_CHIPS_CLAIMED)) == 0 && chips > 0 {
+        store_u8(&key_agent(tid, seat, AF_CHIPS_CLAIMED), 1);
+        chips_paid = chips;
+    }
+
+    let mut prize_paid: u128 = 0;
+    let mut fee_due = false;
+    if max_chips > 0 && chips == max_chips &&
+       load_u8(&key_agent(tid, seat, AF_PRIZE_CLAIMED)) == 0 &&
+       load_u128(&key_table(tid, TF_PRIZE_POOL)) > 0 {
+        let share = prize_share(tid, seat);
+        if share > 0 {
+            fee_due = load_u8(&key_table(tid, TF_PRIZE_FEE_PAID)) == 0;
+            store_u8(&key_table(tid, TF_PRIZE_FEE_PAID), 1);
+            store_u8(&key_agent(tid, seat, AF_PRIZE_CLAIMED), 1);
+            prize_paid = share;
+        }
+    }
+    let payout = chips_paid + prize_paid;
+    if payout == 0 { revert(); }
+
+    // Same ordering as claimPrize: flags first, then transfers
+    if fee_due { pay_pool_fees(tid, load_u128(&key_table(tid, TF_PRIZE_POOL)), FEE_POOL_PRIZE); }
+    pay_out(tid, &caller, payout);
+    if chips_paid > 0 { emit_claim(&CHIPS_CLAIMED_TOPIC, tid, &caller, chips_paid); }
+    if prize_paid > 0 { emit_claim(&PRIZE_CLAIMED_TOPIC, tid, &caller, prize_paid); }
     return_empty();
 }
 
@@ -800,29 +2137,24 @@ fn handle_claim_bet_winnings() -> ! {
     let mut j: u8 = 0;
     while j < bettor_count {
         if addr_eq(&load_addr(&key_bet(tid, j, BF_ADDR)), &caller) {
-            if load_u8(&key_bet(tid, j, BF_CLAIMED)) == 0 {
+            if bet_is_live(tid, j) {
                 let seat = load_u8(&key_bet(tid, j, BF_SEAT));
                 let seat_chips = load_u128(&key_agent(tid, seat, AF_CHIPS));
                 if seat_chips == max_chips {
                     // This bettor backed a winner
-                    let agent_total = load_u128(&key_agent_bet_total(tid, seat));
-
-                    // Pay platform fee on bet pool once
-                    if load_u8(&key_table(tid, TF_BET_FEE_PAID)) == 0 {
-                        store_u8(&key_table(tid, TF_BET_FEE_PAID), 1);
-                        let fee = total_pool * PLATFORM_FEE_BPS / 10_000;
-                        let platform = load_addr(&key_platform());
-                        transfer_to(&platform, fee);
-                    }
-
-                    let net_pool = total_pool - (total_pool * PLATFORM_FEE_BPS / 10_000);
-                    let bet_amt  = load_u128(&key_bet(tid, j, BF_AMOUNT));
-                    let payout   = if agent_total > 0 {
-                        net_pool * bet_amt / agent_total
-                    } else { 0 };
+                    let winners_total = winning_bet_total(tid, max_chips, count);
+                    let bet_amt = load_u128(&key_bet(tid, j, BF_AMOUNT));
+                    let payout  = bet_payout(tid, total_pool, winners_total, bet_amt);
 
+                    // Same ordering as claimPrize: flags first, then transfers
+                    let fee_due = load_u8(&key_table(tid, TF_BET_FEE_PAID)) == 0;
+                    store_u8(&key_table(tid, TF_BET_FEE_PAID), 1);
                     store_u8(&key_bet(tid, j, BF_CLAIMED), 1);
-                    transfer_to(&caller, payout);
+
+                    // Pay platform + creator fees on bet pool once
+                    if fee_due { pay_pool_fees(tid, total_pool, FEE_POOL_BETS); }
+                    pay_out(tid, &caller, payout);
+                    emit_claim(&BET_CLAIMED_TOPIC, tid, &caller, payout);
                     return_empty();
                 }
             }
@@ -832,13 +2164,88 @@ fn handle_claim_bet_winnings() -> ! {
     revert(); // no winning unclaimed bet found
 }
 
+/// claimBetWinnings for every winning live bet the caller holds, paid as
+/// one transfer. Each record gets the same bet_payout as a single claim.
+fn handle_claim_all_bets() -> ! {
+    let tid = read_u32_param(4);
+    if load_u8(&key_table(tid, TF_STATE)) != STATE_ENDED { revert(); }
+
+    let caller = get_caller();
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let (max_chips, _) = find_chip_leaders(tid, count);
+    if max_chips == 0 { revert(); }
+
+    let total_pool = load_u128(&key_table(tid, TF_TOTAL_BET_POOL));
+    if total_pool == 0 { revert(); }
+
+    let winners_total = winning_bet_total(tid, max_chips, count);
+    let mut payout: u128 = 0;
+    let mut found = false;
+    let bettor_count = load_u8(&key_table(tid, TF_BETTOR_COUNT));
+    let mut j: u8 = 0;
+    while j < bettor_count {
+        if addr_eq(&load_addr(&key_bet(tid, j, BF_ADDR)), &caller) && bet_is_live(tid, j) {
+            let seat = load_u8(&key_bet(tid, j, BF_SEAT));
+            if load_u128(&key_agent(tid, seat, AF_CHIPS)) == max_chips {
+                let bet_amt = load_u128(&key_bet(tid, j, BF_AMOUNT));
+                payout += bet_payout(tid, total_pool, winners_total, bet_amt);
+                store_u8(&key_bet(tid, j, BF_CLAIMED), 1);
+                found = true;
+            }
+        }
+        j += 1;
+    }
+    if !found { revert(); }
+
+    let fee_due = load_u8(&key_table(tid, TF_BET_FEE_PAID)) == 0;
+    store_u8(&key_table(tid, TF_BET_FEE_PAID), 1);
+    if fee_due { pay_pool_fees(tid, total_pool, FEE_POOL_BETS); }
+    pay_out(tid, &caller, payout);
+    emit_claim(&BET_CLAIMED_TOPIC, tid, &caller, payout);
+    return_empty();
+}
+
+/// CHIPS_CLAIMED / PRIZE_CLAIMED / BET_CLAIMED data:
+/// tableId(4) | claimant(20) | amount (u128)
+fn emit_claim(topic: &[u8;32], tid: u32, who: &[u8;20], amount: u128) {
+    let mut data = [0u8; 40];
+    data[0..4].copy_from_slice(&tid.to_be_bytes());
+    data[4..24].copy_from_slice(who);
+    data[24..40].copy_from_slice(&amount.to_be_bytes());
+    emit(topic, &data);
+}
+
+/// Winning bettor's share: pro rata by stake of the post-fee pool, against
+/// `winners_total` — everything backing a winning seat (see
+/// winning_bet_total). Shared by claimBetWinnings and quoteBet so quotes
+/// match settlement exactly.
+fn bet_payout(tid: u32, total_pool: u128, winners_total: u128, stake: u128) -> u128 {
+    if winners_total == 0 { return 0; }
+    let net_pool = net_of_fees(tid, total_pool);
+    net_pool * stake / winners_total
+}
+
+/// Bets backing any chip leader. With tied leaders every backer shares
+/// one pool pro rata by stake, rather than each seat's backers splitting
+/// the whole pool among themselves.
+fn winning_bet_total(tid: u32, max_chips: u128, count: u8) -> u128 {
+    let mut total: u128 = 0;
+    let mut i: u8 = 0;
+    while i < count {
+        if load_u128(&key_agent(tid, i, AF_CHIPS)) == max_chips {
+            total += load_u128(&key_agent_bet_total(tid, i));
+        }
+        i += 1;
+    }
+    total
+}
+
 fn handle_refund() -> ! {
     let tid = read_u32_param(4);
     if load_u8(&key_table(tid, TF_STATE)) != STATE_CANCELLED { revert(); }
 
     let caller = get_caller();
     let agent_count = load_u8(&key_table(tid, TF_AGENT_COUNT));
-    let buy_in = load_u128(&key_table(tid, TF_BUY_IN));
 
     // Creator gets prize pool back (checked first)
     let creator = load_addr(&key_table(tid, TF_CREATOR));
@@ -846,19 +2253,20 @@ fn handle_refund() -> ! {
         let pool = load_u128(&key_table(tid, TF_PRIZE_POOL));
         if pool > 0 {
             store_u8(&key_table(tid, TF_PRIZE_FEE_PAID), 1); // repurpose flag as "prize pool refunded"
-            transfer_to(&caller, pool);
+            pay_out(tid, &caller, pool);
             return_empty();
         }
     }
 
-    // Agent buy-in refund (only if not kicked — kicked agents forfeited mid-session; 
-    // but if session was cancelled before play started, kick can't happen)
+    // Agent refund: stack plus its share of an unresolved pot (see
+    // agent_refund). Kicked agents forfeited their stack already.
     let mut i: u8 = 0;
     while i < agent_count {
         if addr_eq(&load_addr(&key_agent(tid, i, AF_ADDR)), &caller) {
-            if load_u8(&key_agent(tid, i, AF_CHIPS_CLAIMED)) == 0 {
+            let due = agent_refund(tid, i);
+            if load_u8(&key_agent(tid, i, AF_CHIPS_CLAIMED)) == 0 && due > 0 {
                 store_u8(&key_agent(tid, i, AF_CHIPS_CLAIMED), 1);
-                transfer_to(&caller, buy_in);
+                pay_out(tid, &caller, due);
                 return_empty();
             }
         }
@@ -870,10 +2278,10 @@ fn handle_refund() -> ! {
     let mut j: u8 = 0;
     while j < bettor_count {
         if addr_eq(&load_addr(&key_bet(tid, j, BF_ADDR)), &caller) {
-            if load_u8(&key_bet(tid, j, BF_CLAIMED)) == 0 {
+            if bet_is_live(tid, j) {
                 let amt = load_u128(&key_bet(tid, j, BF_AMOUNT));
                 store_u8(&key_bet(tid, j, BF_CLAIMED), 1);
-                transfer_to(&caller, amt);
+                pay_out(tid, &caller, amt);
                 return_empty();
             }
         }
@@ -882,6 +2290,110 @@ fn handle_refund() -> ! {
     revert();
 }
 
+/// What a cancelled table owes an agent: its stack, plus — if the table was
+/// cancelled mid-hand (pot not yet awarded) — everything it put into that
+/// pot (hand bet and ante), so the pot unwinds to its contributors. Before
+/// the first deal this is exactly the buy-in. Refunds over all seats plus
+/// the creator's prize pool add up to what the table holds.
+fn agent_refund(tid: u32, seat: u8) -> u128 {
+    let mut due = load_u128(&key_agent(tid, seat, AF_CHIPS));
+    if load_u128(&key_table(tid, TF_POT)) > 0 &&
+       load_u8(&key_agent(tid, seat, AF_DEALT_IN)) != 0 {
+        due += load_u128(&key_agent(tid, seat, AF_HAND_BET));
+        due += load_u128(&key_agent(tid, seat, AF_ANTE_PAID));
+    }
+    due
+}
+
+/// Pull everything owed to the caller from failed pushes. Zeroed before
+/// the call; a failed send reverts, leaving the balance in place.
+fn handle_withdraw_owed() -> ! {
+    let caller = get_caller();
+    let k = key_owed(&caller);
+    let amount = load_u128(&k);
+    if amount == 0 { revert(); }
+    store_u128(&k, 0);
+    let t = key_total_owed();
+    store_u128(&t, load_u128(&t) - amount);
+    if !send_value(&caller, amount) { revert(); }
+    return_empty();
+}
+
+/// withdrawOwed for token payouts of one table that failed to push. Zeroed
+/// before the transfer; a refused transfer reverts, leaving it in place.
+fn handle_withdraw_token_owed() -> ! {
+    let tid = read_u32_param(4);
+    let caller = get_caller();
+    let k = key_token_owed(tid, &caller);
+    let amount = load_u128(&k);
+    if amount == 0 { revert(); }
+    store_u128(&k, 0);
+    let token = load_addr(&key_table(tid, TF_TOKEN));
+    if !token_transfer(&token, &caller, amount) { revert(); }
+    return_empty();
+}
+
+/// Incident switch: while set, createTable, joinTable and bets revert.
+fn handle_set_paused() -> ! {
+    let caller = get_caller();
+    let platform = load_addr(&key_platform());
+    if !addr_eq(&caller, &platform) { revert(); }
+
+    let paused = read_u8_param(4);
+    if paused > 1 { revert(); }
+    store_u8(&key_paused(), paused);
+    emit(&PAUSED_TOPIC, &[paused]);
+    return_empty();
+}
+
+/// Marks every table's accruals in `token` as swept.
+fn bump_sweep_epoch(token: &[u8;20]) {
+    let k = key_sweep_epoch(token);
+    store_u64(&k, load_u64(&k) + 1);
+}
+
+/// Platform pulls every fee accrued across all tables in one transfer.
+fn handle_sweep_fees() -> ! {
+    let caller = get_caller();
+    let platform = load_addr(&key_platform());
+    if !addr_eq(&caller, &platform) { revert(); }
+
+    let k = key_platform_accrued(&[0u8; 20]);
+    let amount = load_u128(&k);
+    if amount == 0 { revert(); }
+    store_u128(&k, 0);
+    bump_sweep_epoch(&[0u8; 20]);
+    if !send_value(&caller, amount) { revert(); }
+
+    let mut data = [0u8; 36];
+    data[0..20].copy_from_slice(&caller);
+    data[20..36].copy_from_slice(&amount.to_be_bytes());
+    emit(&FEES_SWEPT_TOPIC, &data);
+    return_empty();
+}
+
+/// sweepFees for fees accrued in an ERC-20 by token tables.
+fn handle_sweep_token_fees() -> ! {
+    let caller = get_caller();
+    let platform = load_addr(&key_platform());
+    if !addr_eq(&caller, &platform) { revert(); }
+
+    let token = read_addr_param(4);
+    if token == [0u8;20] { revert(); }
+    let k = key_platform_accrued(&token);
+    let amount = load_u128(&k);
+    if amount == 0 { revert(); }
+    store_u128(&k, 0);
+    bump_sweep_epoch(&token);
+    if !token_transfer(&token, &caller, amount) { revert(); }
+
+    let mut data = [0u8; 36];
+    data[0..20].copy_from_slice(&caller);
+    data[20..36].copy_from_slice(&amount.to_be_bytes());
+    emit(&FEES_SWEPT_TOPIC, &data);
+    return_empty();
+}
+
 // ============================================================================
 // VIEW FUNCTIONS
 // ============================================================================
@@ -890,8 +2402,10 @@ fn handle_get_table_info() -> ! {
     let tid = read_u32_param(4);
     // ABI: (address creator, uint256 prizePool, uint256 buyIn, uint8 maxAgents,
     //       uint8 agentCount, uint32 sessionLen, uint32 currentHand,
-    //       uint64 betDeadline, uint8 state) — 9 slots × 32 bytes
-    let mut r = [0u8; 288];
+    //       uint64 betDeadline, uint8 state, uint16 creatorFeeBps,
+    //       uint8 tiePolicy, uint8 kickThreshold, uint64 inactivitySecs)
+    //       — 13 slots × 32 bytes
+    let mut r = [0u8; 416];
     let c = load_addr(&key_table(tid, TF_CREATOR));
     r[12..32].copy_from_slice(&c);
 
@@ -914,14 +2428,93 @@ fn handle_get_table_info() -> ! {
     r[248..256].copy_from_slice(&bd.to_be_bytes());
 
     r[287] = load_u8(&key_table(tid, TF_STATE));
+
+    let cf = load_u32(&key_table(tid, TF_CREATOR_FEE_BPS));
+    r[316..320].copy_from_slice(&cf.to_be_bytes());
+
+    r[351] = load_u8(&key_table(tid, TF_TIE_POLICY));
+    r[383] = table_kick_threshold(tid);
+    r[408..416].copy_from_slice(&table_inactivity_secs(tid).to_be_bytes());
     api::return_value(ReturnFlags::empty(), &r);
 }
 
+/// Lobby page: summaries for ids [start, start+count), clamped to the
+/// existing tables and to MAX_TABLES_PAGE.
+fn handle_get_tables() -> ! {
+    let start = read_u32_param(4);
+    let count = read_u8_param(36) as u32;
+    let total = load_u32(&key_table_count());
+
+    let mut n = if start >= total { 0 } else { total - start };
+    if n > count           { n = count; }
+    if n > MAX_TABLES_PAGE { n = MAX_TABLES_PAGE; }
+
+    // ABI: TableSummary[] where TableSummary = (uint32 id, uint8 state,
+    //      uint8 agentCount, uint256 buyIn, uint256 prizePool) — a static
+    //      tuple, so rows are inlined after the offset + length words
+    const ROW: usize = 5 * 32;
+    let mut r = [0u8; 64 + ROW * MAX_TABLES_PAGE as usize];
+    r[31] = 0x20;
+    r[60..64].copy_from_slice(&n.to_be_bytes());
+    let mut i: u32 = 0;
+    while i < n {
+        let tid = start + i;
+        let o = 64 + ROW * i as usize;
+        r[o + 28..o + 32].copy_from_slice(&tid.to_be_bytes());
+        r[o + 63]  = load_u8(&key_table(tid, TF_STATE));
+        r[o + 95]  = load_u8(&key_table(tid, TF_AGENT_COUNT));
+        let bi = load_u128(&key_table(tid, TF_BUY_IN));
+        r[o + 112..o + 128].copy_from_slice(&bi.to_be_bytes());
+        let pp = load_u128(&key_table(tid, TF_PRIZE_POOL));
+        r[o + 144..o + 160].copy_from_slice(&pp.to_be_bytes());
+        i += 1;
+    }
+    api::return_value(ReturnFlags::empty(), &r[..64 + ROW * n as usize]);
+}
+
+/// Lobby filter: ids >= start whose state matches, up to `limit` rows
+/// (clamped to MAX_TABLES_PAGE). At most MAX_STATE_SCAN ids are examined,
+/// so a sparse match still returns; page on with the returned cursor until
+/// it reaches tableCount.
+fn handle_get_tables_by_state() -> ! {
+    let want  = read_u8_param(4);
+    let start = read_u32_param(36);
+    let mut limit = read_u8_param(68) as u32;
+    if limit > MAX_TABLES_PAGE { limit = MAX_TABLES_PAGE; }
+    let total = load_u32(&key_table_count());
+    let end = if total.saturating_sub(start) > MAX_STATE_SCAN { start + MAX_STATE_SCAN } else { total };
+
+    // ABI: (TableMatch[] rows, uint32 next) where TableMatch = (uint32 id,
+    //      uint8 agentCount, uint256 buyIn) — static rows inlined after the
+    //      head (offset, next) and the length word
+    const ROW: usize = 3 * 32;
+    let mut r = [0u8; 96 + ROW * MAX_TABLES_PAGE as usize];
+    r[31] = 0x40;
+    let mut n: u32 = 0;
+    let mut tid = start;
+    while tid < end && n < limit {
+        if load_u8(&key_table(tid, TF_STATE)) == want {
+            let o = 96 + ROW * n as usize;
+            r[o + 28..o + 32].copy_from_slice(&tid.to_be_bytes());
+            r[o + 63] = load_u8(&key_table(tid, TF_AGENT_COUNT));
+            let bi = load_u128(&key_table(tid, TF_BUY_IN));
+            r[o + 80..o + 96].copy_from_slice(&bi.to_be_bytes());
+            n += 1;
+        }
+        tid += 1;
+    }
+    let next = tid.max(start);
+    r[60..64].copy_from_slice(&next.to_be_bytes());
+    r[92..96].copy_from_slice(&n.to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r[..96 + ROW * n as usize]);
+}
+
 fn handle_get_agent_info() -> ! {
     let tid  = read_u32_param(4);
     let seat = read_u8_param(36);
-    // ABI: (address agent, uint256 chips, bool folded, bool kicked, uint8 missedTurns)
-    let mut r = [0u8; 160];
+    // ABI: (address agent, uint256 chips, bool folded, bool kicked, uint8 missedTurns,
+    //       uint256 rebought)
+    let mut r = [0u8; 192];
     let a = load_addr(&key_agent(tid, seat, AF_ADDR));
     r[12..32].copy_from_slice(&a);
     let chips = load_u128(&key_agent(tid, seat, AF_CHIPS));
@@ -929,6 +2522,32 @@ fn handle_get_agent_info() -> ! {
     r[95]  = load_u8(&key_agent(tid, seat, AF_FOLDED));
     r[127] = load_u8(&key_agent(tid, seat, AF_KICKED));
     r[159] = load_u8(&key_agent(tid, seat, AF_MISSED));
+    let rebought = load_u128(&key_agent(tid, seat, AF_REBOUGHT));
+    r[176..192].copy_from_slice(&rebought.to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+/// Every seat's address, chips, folded, kicked and missedTurns in one call.
+/// ABI: (uint8 agentCount, AgentRow[8] rows) with AgentRow = (address,
+/// uint256 chips, bool folded, bool kicked, uint8 missedTurns); rows past
+/// agentCount are zero.
+fn handle_get_all_agents() -> ! {
+    let tid   = read_u32_param(4);
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT)).min(MAX_AGENTS);
+    const ROW: usize = 5 * 32;
+    let mut r = [0u8; 32 + ROW * MAX_AGENTS as usize];
+    r[31] = count;
+    let mut i: u8 = 0;
+    while i < count {
+        let o = 32 + ROW * i as usize;
+        r[o + 12..o + 32].copy_from_slice(&load_addr(&key_agent(tid, i, AF_ADDR)));
+        let chips = load_u128(&key_agent(tid, i, AF_CHIPS));
+        r[o + 48..o + 64].copy_from_slice(&chips.to_be_bytes());
+        r[o + 95]  = load_u8(&key_agent(tid, i, AF_FOLDED));
+        r[o + 127] = load_u8(&key_agent(tid, i, AF_KICKED));
+        r[o + 159] = load_u8(&key_agent(tid, i, AF_MISSED));
+        i += 1;
+    }
     api::return_value(ReturnFlags::empty(), &r);
 }
 
@@ -951,6 +2570,40 @@ fn handle_get_bet_info() -> ! {
     api::return_value(ReturnFlags::empty(), &r);
 }
 
+/// Raw bet record for paging 0..bettorCount — the address lookup above only
+/// ever sees an address's first bet.
+fn handle_get_bet_by_index() -> ! {
+    let tid = read_u32_param(4);
+    let idx = read_u8_param(36);
+    if idx >= load_u8(&key_table(tid, TF_BETTOR_COUNT)) { revert(); }
+
+    // ABI: (address bettor, uint8 seat, uint256 amount, bool claimed, bool cancelled)
+    let mut r = [0u8; 160];
+    r[12..32].copy_from_slice(&load_addr(&key_bet(tid, idx, BF_ADDR)));
+    r[63]  = load_u8(&key_bet(tid, idx, BF_SEAT));
+    let amt = load_u128(&key_bet(tid, idx, BF_AMOUNT));
+    r[80..96].copy_from_slice(&amt.to_be_bytes());
+    r[127] = load_u8(&key_bet(tid, idx, BF_CLAIMED));
+    r[159] = load_u8(&key_bet(tid, idx, BF_CANCELLED));
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+/// Projected payout if `amount` were bet on `seat` now and `seat` won:
+/// the stake is added to both the seat total and the pool before the split.
+fn handle_quote_bet() -> ! {
+    let tid    = read_u32_param(4);
+    let seat   = read_u8_param(36);
+    let amount = read_u256_as_u128(68);
+    if seat >= load_u8(&key_table(tid, TF_AGENT_COUNT)) { revert(); }
+
+    let pool       = load_u128(&key_table(tid, TF_TOTAL_BET_POOL)) + amount;
+    // Outright win: the seat's backers are the only winners
+    let seat_total = load_u128(&key_agent_bet_total(tid, seat)) + amount;
+    let payout = bet_payout(tid, pool, seat_total, amount);
+    let mut r = [0u8;32]; r[16..].copy_from_slice(&payout.to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
 fn handle_get_agent_bet_total() -> ! {
     let tid  = read_u32_param(4);
     let seat = read_u8_param(36);
@@ -959,6 +2612,16 @@ fn handle_get_agent_bet_total() -> ! {
     api::return_value(ReturnFlags::empty(), &r);
 }
 
+/// Sum of the address's live bets on the table, across all seats.
+/// Maintained alongside the per-seat totals so the read is O(1).
+fn handle_get_bettor_total() -> ! {
+    let tid    = read_u32_param(4);
+    let bettor = read_addr_param(36);
+    let total  = load_u128(&key_bettor_total(tid, &bettor));
+    let mut r = [0u8;32]; r[16..].copy_from_slice(&total.to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
 fn handle_get_total_bet_pool() -> ! {
     let tid   = read_u32_param(4);
     let total = load_u128(&key_table(tid, TF_TOTAL_BET_POOL));
@@ -972,3 +2635,355 @@ fn handle_get_current_pot() -> ! {
     let mut r = [0u8;32]; r[16..].copy_from_slice(&pot.to_be_bytes());
     api::return_value(ReturnFlags::empty(), &r);
 }
+
+/// Live hand state. `activeCount` = still in this hand (not folded, not
+/// kicked; drives turn rotation), `aliveCount` = still in the session (not
+/// kicked; drives session end). `button` is this hand's dealer-button seat;
+/// the blinds and first to act sit to its left.
+fn handle_get_game_state() -> ! {
+    let tid = read_u32_param(4);
+    // ABI: (uint8 state, uint32 currentHand, uint8 currentTurn,
+    //       uint256 currentBet, uint256 pot, uint8 activeCount, uint8 aliveCount,
+    //       uint8 button)
+    let mut r = [0u8; 256];
+    r[31] = load_u8(&key_table(tid, TF_STATE));
+    r[60..64].copy_from_slice(&load_u32(&key_table(tid, TF_CURRENT_HAND)).to_be_bytes());
+    r[95] = load_u8(&key_table(tid, TF_CURRENT_TURN));
+    r[112..128].copy_from_slice(&load_u128(&key_table(tid, TF_CURRENT_BET)).to_be_bytes());
+    r[144..160].copy_from_slice(&load_u128(&key_table(tid, TF_POT)).to_be_bytes());
+    r[191] = load_u8(&key_table(tid, TF_ACTIVE_COUNT));
+    r[223] = load_u8(&key_table(tid, TF_ALIVE_COUNT));
+    r[255] = load_u8(&key_table(tid, TF_BUTTON));
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+fn handle_get_round_complete() -> ! {
+    let tid = read_u32_param(4);
+    let mut r = [0u8; 32];
+    r[31] = round_complete(tid) as u8;
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+/// Per-seat flags for the current hand (HS_* bits), as a fixed uint8[8]
+/// indexed by seat; seats past agentCount read 0.
+fn handle_get_hand_status() -> ! {
+    let tid = read_u32_param(4);
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let mut r = [0u8; 32 * MAX_AGENTS as usize];
+    let mut i: u8 = 0;
+    while i < count {
+        let dealt  = load_u8(&key_agent(tid, i, AF_DEALT_IN)) != 0;
+        let folded = load_u8(&key_agent(tid, i, AF_FOLDED)) != 0;
+        let kicked = load_u8(&key_agent(tid, i, AF_KICKED)) != 0;
+        let mut s: u8 = 0;
+        if dealt  { s |= HS_DEALT_IN; }
+        if folded { s |= HS_FOLDED; }
+        if kicked { s |= HS_KICKED; }
+        if load_u8(&key_agent(tid, i, AF_ACTED)) != 0 { s |= HS_ACTED; }
+        if dealt && !folded && !kicked &&
+           load_u128(&key_agent(tid, i, AF_CHIPS)) == 0 &&
+           load_u128(&key_agent(tid, i, AF_HAND_BET)) > 0 { s |= HS_ALL_IN; }
+        r[32 * i as usize + 31] = s;
+        i += 1;
+    }
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+/// (bool myTurn, uint256 toCall) for the caller. Same seat/address check
+/// as handle_action; toCall is what a call would cost that seat right now.
+fn handle_is_my_turn() -> ! {
+    let tid  = read_u32_param(4);
+    let turn = load_u8(&key_table(tid, TF_CURRENT_TURN));
+    let mine = load_u8(&key_table(tid, TF_STATE)) == STATE_PLAYING &&
+        addr_eq(&get_caller(), &load_addr(&key_agent(tid, turn, AF_ADDR)));
+
+    let mut r = [0u8; 64];
+    if mine {
+        r[31] = 1;
+        let cur_bet   = load_u128(&key_table(tid, TF_CURRENT_BET));
+        let agent_bet = load_u128(&key_agent(tid, turn, AF_HAND_BET));
+        r[48..64].copy_from_slice(&cur_bet.saturating_sub(agent_bet).to_be_bytes());
+    }
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+/// One-call standings for an Ended table. ABI: (uint256[8] chips,
+/// uint8 leaderMask, uint8 leaderCount, uint256 prizePool,
+/// uint256 payoutPerWinner). Chips are final stacks (claimChips doesn't
+/// zero them); leaders are the seats claimPrize pays under the tie policy,
+/// and the payout is that share net of both fees.
+fn handle_get_final_results() -> ! {
+    let tid = read_u32_param(4);
+    if load_u8(&key_table(tid, TF_STATE)) != STATE_ENDED { revert(); }
+
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let prize_pool = load_u128(&key_table(tid, TF_PRIZE_POOL));
+
+    let mut r = [0u8; 32 * (MAX_AGENTS as usize + 4)];
+    let mut mask: u8 = 0;
+    let mut winners: u8 = 0;
+    let mut payout: u128 = 0;
+    let mut i: u8 = 0;
+    while i < count {
+        let chips = load_u128(&key_agent(tid, i, AF_CHIPS));
+        let o = 32 * i as usize;
+        r[o + 16..o + 32].copy_from_slice(&chips.to_be_bytes());
+        // The last paid seat never holds the EvenSplit remainder, so its
+        // share is the base payout every winner gets
+        let share = prize_share(tid, i);
+        if share > 0 { mask |= 1 << i; winners += 1; payout = share; }
+        i += 1;
+    }
+
+    let o = 32 * MAX_AGENTS as usize;
+    r[o + 31] = mask;
+    r[o + 63] = winners;
+    r[o + 80..o + 96].copy_from_slice(&prize_pool.to_be_bytes());
+    r[o + 112..o + 128].copy_from_slice(&payout.to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+/// Solvency audit: what the table still owes, mirroring each claim path's
+/// own flags. ABI: (uint256 total, uint256 prize, uint256 chips, uint256 bets).
+/// Prize and bets include not-yet-paid fees; burned forfeits are excluded.
+fn handle_get_table_liabilities() -> ! {
+    let tid   = read_u32_param(4);
+    let state = load_u8(&key_table(tid, TF_STATE));
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let prize_pool = load_u128(&key_table(tid, TF_PRIZE_POOL));
+    let bet_pool   = load_u128(&key_table(tid, TF_TOTAL_BET_POOL));
+    let prize_open = load_u8(&key_table(tid, TF_PRIZE_FEE_PAID)) == 0;
+    let bets_open  = load_u8(&key_table(tid, TF_BET_FEE_PAID)) == 0;
+    let (max_chips, _) = find_chip_leaders(tid, count);
+
+    let mut prize: u128 = 0;
+    let mut chips: u128 = 0;
+    let mut bets:  u128 = 0;
+
+    // Prize: whole pool until the first payout, then unclaimed winner shares
+    if prize_open {
+        prize = prize_pool;
+    } else if state == STATE_ENDED {
+        let mut i: u8 = 0;
+        while i < count {
+            if load_u8(&key_agent(tid, i, AF_PRIZE_CLAIMED)) == 0 { prize += prize_share(tid, i); }
+            i += 1;
+        }
+    }
+
+    // Chips: live stacks + pot while playing, unclaimed stacks once Ended,
+    // unrefunded stacks plus the unwound pot once Cancelled
+    let mut i: u8 = 0;
+    while i < count {
+        if load_u8(&key_agent(tid, i, AF_CHIPS_CLAIMED)) == 0 {
+            chips += if state == STATE_CANCELLED { agent_refund(tid, i) }
+                     else { load_u128(&key_agent(tid, i, AF_CHIPS)) };
+        }
+        i += 1;
+    }
+    if state == STATE_OPEN || state == STATE_PLAYING {
+        chips += load_u128(&key_table(tid, TF_POT));
+    }
+
+    // Bets: live bets until settlement starts, then unclaimed winning shares
+    let bettor_count = load_u8(&key_table(tid, TF_BETTOR_COUNT));
+    if state == STATE_CANCELLED {
+        let mut j: u8 = 0;
+        while j < bettor_count {
+            if bet_is_live(tid, j) { bets += load_u128(&key_bet(tid, j, BF_AMOUNT)); }
+            j += 1;
+        }
+    } else if bets_open {
+        bets = bet_pool;
+    } else {
+        let winners_total = winning_bet_total(tid, max_chips, count);
+        let mut j: u8 = 0;
+        while j < bettor_count {
+            let seat = load_u8(&key_bet(tid, j, BF_SEAT));
+            if bet_is_live(tid, j) && load_u128(&key_agent(tid, seat, AF_CHIPS)) == max_chips {
+                bets += bet_payout(tid, bet_pool, winners_total,
+                                   load_u128(&key_bet(tid, j, BF_AMOUNT)));
+            }
+            j += 1;
+        }
+    }
+
+    let mut r = [0u8; 128];
+    r[16..32].copy_from_slice(&(prize + chips + bets).to_be_bytes());
+    r[48..64].copy_from_slice(&prize.to_be_bytes());
+    r[80..96].copy_from_slice(&chips.to_be_bytes());
+    r[112..128].copy_from_slice(&bets.to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+fn handle_get_owed() -> ! {
+    let addr = read_addr_param(4);
+    let mut r = [0u8; 32];
+    r[16..32].copy_from_slice(&load_u128(&key_owed(&addr)).to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+fn handle_get_token_owed() -> ! {
+    let tid  = read_u32_param(4);
+    let addr = read_addr_param(36);
+    let mut r = [0u8; 32];
+    r[16..32].copy_from_slice(&load_u128(&key_token_owed(tid, &addr)).to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+/// Everything `who` could still pull from the table right now, using the
+/// same math as the claim handlers: once Ended, unclaimed chips + prize
+/// share + winning bets; once Cancelled, what refund would return (prize
+/// pool, agent refund, live bets). 0 otherwise. Excludes the owed balance.
+fn handle_get_claimable() -> ! {
+    let tid = read_u32_param(4);
+    let who = read_addr_param(36);
+    let state = load_u8(&key_table(tid, TF_STATE));
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let seat  = seat_of(tid, &who);
+    let bettor_count = load_u8(&key_table(tid, TF_BETTOR_COUNT));
+    let mut total: u128 = 0;
+
+    if state == STATE_ENDED {
+        let (max_chips, _) = find_chip_leaders(tid, count);
+        if seat != NO_SEAT {
+            if load_u8(&key_agent(tid, seat, AF_CHIPS_CLAIMED)) == 0 {
+                total += load_u128(&key_agent(tid, seat, AF_CHIPS));
+            }
+            if load_u8(&key_agent(tid, seat, AF_PRIZE_CLAIMED)) == 0 {
+                total += prize_share(tid, seat);
+            }
+        }
+        let total_pool = load_u128(&key_table(tid, TF_TOTAL_BET_POOL));
+        let winners_total = winning_bet_total(tid, max_chips, count);
+        let mut j: u8 = 0;
+        while max_chips > 0 && j < bettor_count {
+            if addr_eq(&load_addr(&key_bet(tid, j, BF_ADDR)), &who) && bet_is_live(tid, j) {
+                let s = load_u8(&key_bet(tid, j, BF_SEAT));
+                if load_u128(&key_agent(tid, s, AF_CHIPS)) == max_chips {
+                    total += bet_payout(tid, total_pool, winners_total,
+                                        load_u128(&key_bet(tid, j, BF_AMOUNT)));
+                }
+            }
+            j += 1;
+        }
+    } else if state == STATE_CANCELLED {
+        if addr_eq(&who, &load_addr(&key_table(tid, TF_CREATOR))) &&
+           load_u8(&key_table(tid, TF_PRIZE_FEE_PAID)) == 0 {
+            total += load_u128(&key_table(tid, TF_PRIZE_POOL));
+        }
+        if seat != NO_SEAT && load_u8(&key_agent(tid, seat, AF_CHIPS_CLAIMED)) == 0 {
+            total += agent_refund(tid, seat);
+        }
+        let mut j: u8 = 0;
+        while j < bettor_count {
+            if addr_eq(&load_addr(&key_bet(tid, j, BF_ADDR)), &who) && bet_is_live(tid, j) {
+                total += load_u128(&key_bet(tid, j, BF_AMOUNT));
+            }
+            j += 1;
+        }
+    }
+
+    let mut r = [0u8; 32];
+    r[16..32].copy_from_slice(&total.to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+/// Gross value the table brought in and still accounts for: prize pool +
+/// bet pool + every seat's stack + the pot. Until settlement starts this
+/// is what the contract holds for the table; once claims begin use
+/// getTableLiabilities, which nets out what has been paid.
+fn handle_get_table_tvl() -> ! {
+    let tid = read_u32_param(4);
+    let count = load_u8(&key_table(tid, TF_AGENT_COUNT));
+    let mut tvl = load_u128(&key_table(tid, TF_PRIZE_POOL))
+                + load_u128(&key_table(tid, TF_TOTAL_BET_POOL))
+                + load_u128(&key_table(tid, TF_POT));
+    let mut i: u8 = 0;
+    while i < count {
+        tvl += load_u128(&key_agent(tid, i, AF_CHIPS));
+        i += 1;
+    }
+    let mut r = [0u8; 32];
+    r[16..32].copy_from_slice(&tvl.to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+/// Tables created so far; ids run 0..count
+fn handle_get_table_count() -> ! {
+    let mut r = [0u8; 32];
+    r[28..32].copy_from_slice(&load_u32(&key_table_count()).to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+fn handle_get_turn_deadline() -> ! {
+    let tid = read_u32_param(4);
+    let mut r = [0u8; 32];
+    r[24..32].copy_from_slice(&load_u64(&key_table(tid, TF_TURN_DEADLINE)).to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+fn handle_get_seat_of() -> ! {
+    let tid  = read_u32_param(4);
+    let addr = read_addr_param(36);
+    return_u8(seat_of(tid, &addr));
+}
+
+fn handle_get_dealer() -> ! {
+    let mut r = [0u8; 32];
+    r[12..32].copy_from_slice(&load_addr(&key_dealer()));
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+fn handle_get_platform() -> ! {
+    let mut r = [0u8; 32];
+    r[12..32].copy_from_slice(&load_addr(&key_platform()));
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+fn handle_get_buy_in_bounds() -> ! {
+    // ABI: (uint256 minBuyIn, uint256 maxBuyIn)
+    let mut r = [0u8; 64];
+    r[16..32].copy_from_slice(&load_u128(&key_min_buy_in()).to_be_bytes());
+    r[48..64].copy_from_slice(&load_u128(&key_max_buy_in()).to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+fn handle_get_paused() -> ! {
+    let mut r = [0u8; 32];
+    r[31] = load_u8(&key_paused());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+fn handle_get_table_token() -> ! {
+    let tid = read_u32_param(4);
+    let mut r = [0u8; 32];
+    r[12..32].copy_from_slice(&load_addr(&key_table(tid, TF_TOKEN)));
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+fn handle_get_platform_accrued() -> ! {
+    let mut r = [0u8; 32];
+    r[16..32].copy_from_slice(&load_u128(&key_platform_accrued(&[0u8; 20])).to_be_bytes());
+    api::return_value(ReturnFlags::empty(), &r);
+}
+
+fn handle_get_fee_status() -> ! {
+    let tid = read_u32_param(4);
+    // ABI: (bool prizeFeePaid, bool betFeePaid, uint256 prizeFee, uint256 betFee,
+    //       uint256 unsweptFee)
+    // On a Cancelled table prizeFeePaid means "prize pool refunded to creator".
+    let mut r = [0u8; 160];
+    r[31] = load_u8(&key_table(tid, TF_PRIZE_FEE_PAID));
+    r[63] = load_u8(&key_table(tid, TF_BET_FEE_PAID));
+    let prize_fee = platform_fee_of(tid, load_u128(&key_table(tid, TF_PRIZE_POOL)));
+    r[80..96].copy_from_slice(&prize_fee.to_be_bytes());
+    let bet_fee = platform_fee_of(tid, load_u128(&key_table(tid, TF_TOTAL_BET_POOL)));
+    r[112..128].copy_from_slice(&bet_fee.to_be_bytes());
+    // This table's platform fees still sitting in platform_accrued
+    let token = load_addr(&key_table(tid, TF_TOKEN));
+    if load_u64(&key_table(tid, TF_FEE_EPOCH)) == load_u64(&key_sweep_epoch(&token)) {
+        r[144..160].copy_from_slice(&load_u128(&key_table(tid, TF_FEE_ACCRUED)).to_be_bytes());
+    }
+    api::return_value(ReturnFlags::empty(), &r);
+}
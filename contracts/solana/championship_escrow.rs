@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
 use anchor_lang::system_program;
 
 declare_id!("AvNFV1Bg6ZfngTuGdd5uDDxV22nsmumYd3JUpkQu9MPT");
 
 // ─── Constants ───────────────────────────────────────────────────────
 pub const MAX_AGENTS: usize = 64;
-pub const MIN_FEE: u64 = 10_000_000;             // 0.01 SOL in lamports
-pub const MIN_AGENTS: u32 = 3;
-pub const MIN_VOTE_BALANCE: u64 = 1_000_000_000; // 1 SOL in lamports
+// Defaults for the program-wide Config (passing 0 at init selects these)
+pub const DEFAULT_MIN_FEE: u64 = 10_000_000;             // 0.01 SOL in lamports
+pub const DEFAULT_MIN_AGENTS: u32 = 3;
+pub const DEFAULT_MIN_VOTE_BALANCE: u64 = 1_000_000_000; // 1 SOL in lamports
 
 pub const EW: u64 = 95; // entry pool → winner agent owner %
 pub const EC: u64 = 4;  // entry pool → creator %
@@ -18,6 +20,9 @@ pub const BP: u64 = 3;  // bet pool   → platform %
 
 pub const REFUND_PCT: u64 = 98; // % of entry fee returned on withdraw
 pub const PEEK_FEE_PCT: u64 = 2; // % of entry fee kept as peek fee (→ platform)
+pub const CLOSE_GRACE_SECS: i64 = 30 * 24 * 3600; // after judge_end, unclaimed refunds no longer block close
+pub const MARKET_PAGE: usize = 16;          // entries per get_betting_market call (1 KiB return data)
+pub const MULTIPLE_SCALE: u64 = 10_000;     // implied_payout_multiple fixed point (10_000 = 1.0x)
 
 // PDA seeds
 pub const CHALLENGE_SEED: &[u8] = b"challenge";
@@ -27,6 +32,7 @@ pub const BET_SEED: &[u8] = b"bet";
 pub const USER_BET_TOTAL_SEED: &[u8] = b"user_bet_total";
 pub const VOTE_RECORD_SEED: &[u8] = b"vote_record";
 pub const CLAIM_RECORD_SEED: &[u8] = b"claim_record";
+pub const CONFIG_SEED: &[u8] = b"config";
 
 // ─── Errors ──────────────────────────────────────────────────────────
 #[error_code]
@@ -47,7 +53,7 @@ pub enum EscrowError {
     AgentTaken,           // 6006
     #[msg("E11: Challenge not active")]
     NotActive,            // 6007
-    #[msg("E12: Fewer than 3 agents enrolled")]
+    #[msg("E12: Fewer than min_agents active agents")]
     TooFewAgents,         // 6008
     #[msg("E13: Wrong phase for this action")]
     WrongPhase,           // 6009
@@ -63,7 +69,7 @@ pub enum EscrowError {
     LowBalance,           // 6014
     #[msg("E19: Required period has not ended yet")]
     NotEnded,             // 6015
-    #[msg("E20: Cannot cancel — enough agents enrolled")]
+    #[msg("E20: Cannot cancel — quorum met and challenge revealed")]
     CannotCancel,         // 6016
     #[msg("E22: Challenge neither finalized nor cancelled")]
     NotDone,              // 6017
@@ -83,6 +89,32 @@ pub enum EscrowError {
     AlreadyWithdrawn,     // 6024
     #[msg("E30: Not the agent owner")]
     NotAgentOwner,        // 6025
+    #[msg("E31: Not the challenge creator")]
+    NotCreator,           // 6026
+    #[msg("E32: Not the challenge platform")]
+    NotPlatform,          // 6027
+    #[msg("E33: Not the pending platform")]
+    NotPendingPlatform,   // 6028
+    #[msg("E34: Record does not belong to caller")]
+    InvalidRecord,        // 6029
+    #[msg("E35: Not the config admin")]
+    NotAdmin,             // 6030
+    #[msg("E36: Invalid config value")]
+    BadConfig,            // 6031
+    #[msg("E37: Agent ID must be non-zero")]
+    InvalidAgentId,       // 6032
+    #[msg("E38: Refunds still unclaimed")]
+    RefundsOutstanding,   // 6033
+    #[msg("E39: Challenge not yet revealed")]
+    NotRevealed,          // 6034
+    #[msg("E40: Revealed challenge does not match challenge_hash")]
+    BadReveal,            // 6035
+    #[msg("E41: min_agents must be between 2 and max_agents")]
+    BadMinAgents,         // 6036
+    #[msg("E42: Payout would leave the vault below rent exemption")]
+    VaultBelowRent,       // 6037
+    #[msg("E43: winner_index out of bounds")]
+    WinnerOutOfBounds,    // 6038
 }
 
 // ─── Events ──────────────────────────────────────────────────────────
@@ -97,6 +129,11 @@ pub struct ChallengeCreated {
     pub challenge_hash: [u8; 32],
     pub competition_duration: i64,
     pub refund_duration: i64,
+    // Full config snapshot, so the challenge can be rebuilt from logs alone
+    pub max_agents: u32,
+    pub min_agents: u32,
+    pub entry_split: [u8; 3], // winner / creator / platform %
+    pub bet_split: [u8; 3],   // winners / creator / platform %
 }
 
 #[event]
@@ -134,6 +171,20 @@ pub struct ChallengeCancelled {
     pub challenge_id: [u8; 32],
 }
 
+#[event]
+pub struct ChallengeClosed {
+    pub challenge_id: [u8; 32],
+    pub creator_rent: u64,
+    pub unclaimed_refunds: u64, // refunds nobody claimed within CLOSE_GRACE_SECS (→ platform)
+    pub platform_dust: u64,
+}
+
+#[event]
+pub struct ChallengeRevealed {
+    pub challenge_id: [u8; 32],
+    pub revealed_at: i64,
+}
+
 #[event]
 pub struct PayoutClaimed {
     pub challenge_id: [u8; 32],
@@ -141,6 +192,49 @@ pub struct PayoutClaimed {
     pub amount: u64,
 }
 
+#[event]
+pub struct RecordsClosed {
+    pub challenge_id: [u8; 32],
+    pub owner: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct VoteStakeReleased {
+    pub challenge_id: [u8; 32],
+    pub voter: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreatorTransferred {
+    pub challenge_id: [u8; 32],
+    pub previous: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct PlatformTransferProposed {
+    pub challenge_id: [u8; 32],
+    pub platform: Pubkey,
+    pub pending: Pubkey,
+}
+
+#[event]
+pub struct PlatformTransferred {
+    pub challenge_id: [u8; 32],
+    pub previous: Pubkey,
+    pub platform: Pubkey,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub min_fee: u64,
+    pub min_agents: u32,
+    pub min_vote_balance: u64,
+}
+
 #[event]
 pub struct AgentWithdrawnEvent {
     pub challenge_id: [u8; 32],
@@ -160,6 +254,7 @@ pub struct AgentWithdrawnEvent {
 pub struct Challenge {
     pub creator: Pubkey,            // 32
     pub platform: Pubkey,           // 32
+    pub pending_platform: Pubkey,   // 32 (default = no transfer in flight)
     pub challenge_id: [u8; 32],     // 32
 
     pub entry_fee: u64,             // 8
@@ -173,16 +268,28 @@ pub struct Challenge {
 
     pub total_entry_pool: u64,      // 8
     pub total_bet_pool: u64,        // 8
+    pub total_refunded: u64,        // 8  (cancelled refunds paid so far)
+    pub vault_reserve: u64,         // 8  (vault's rent-exempt minimum, funded by the creator at create)
+
+    // Frozen at finalize so claim denominators can't drift afterwards
+    pub final_total_bet_pool: u64,  // 8
+    pub final_winner_bet_pool: u64, // 8
 
     pub agent_count: u32,           // 4
+    pub min_agents: u32,            // 4  (create arg, or Config.min_agents if 0)
     pub finalized: bool,            // 1
     pub cancelled: bool,            // 1
+    pub revealed: bool,             // 1  (full_challenge matched challenge_hash)
+    pub closed: bool,               // 1  (close_cancelled drained the vault; account kept as tombstone)
     pub winner_index: u8,           // 1 (index into agent arrays)
 
     pub bump: u8,                   // 1
     pub vault_bump: u8,             // 1
 
-    // Parallel arrays (all indexed by agent position)
+    // Parallel arrays (all indexed by agent position). Append-only: an
+    // agent's index is fixed at enroll and never reused or compacted away —
+    // withdraw only sets `withdrawn[i]` — so `winner_index` and any index a
+    // client cached stay valid for the challenge's lifetime.
     pub agent_ids: Vec<[u8; 32]>,       // 4 + 32*N
     pub agent_owners: Vec<Pubkey>,      // 4 + 32*N
     pub vote_counts: Vec<u64>,          // 4 +  8*N
@@ -191,11 +298,134 @@ pub struct Challenge {
 }
 
 impl Challenge {
+    /// Scalar-only view of this challenge.
+    pub fn header(&self) -> ChallengeHeader {
+        ChallengeHeader {
+            creator: self.creator,
+            platform: self.platform,
+            entry_fee: self.entry_fee,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            judge_end: self.judge_end,
+            total_entry_pool: self.total_entry_pool,
+            total_bet_pool: self.total_bet_pool,
+            agent_count: self.agent_count,
+            active_agent_count: self.active_agent_count(),
+            min_agents: self.min_agents,
+            finalized: self.finalized,
+            cancelled: self.cancelled,
+            revealed: self.revealed,
+            closed: self.closed,
+            winner_index: self.winner_index,
+        }
+    }
+
+    /// How `close_cancelled` splits a vault holding `vault_balance`:
+    /// `(creator, unclaimed, dust)`. The creator gets back the reserve they
+    /// funded at create; refunds still `outstanding` and anything left over
+    /// go to the platform.
+    pub fn close_split(&self, vault_balance: u64, outstanding: u64) -> (u64, u64, u64) {
+        let creator = self.vault_reserve.min(vault_balance);
+        let rest = vault_balance - creator;
+        let unclaimed = outstanding.min(rest);
+        (creator, unclaimed, rest - unclaimed)
+    }
+
+    /// What a finalized `claim` pays `claimant`, who bet `winner_bet` on the
+    /// winning agent (0 without a BetRecord). The winner-owner, creator and
+    /// winning-bettor shares are independent and add up.
+    pub fn finalized_payout(&self, claimant: &Pubkey, winner_bet: u64) -> Result<u64> {
+        let mut payout: u64 = 0;
+
+        // 1) Winner agent owner → 95% of entry pool
+        if *claimant == self.winner_owner()? {
+            let entry_winner = self
+                .total_entry_pool
+                .checked_mul(EW)
+                .ok_or(EscrowError::Overflow)?
+                / 100;
+            payout = payout
+                .checked_add(entry_winner)
+                .ok_or(EscrowError::Overflow)?;
+        }
+
+        // 2) Creator → 4% entry + 2% bets
+        if *claimant == self.creator {
+            let entry_creator = self
+                .total_entry_pool
+                .checked_mul(EC)
+                .ok_or(EscrowError::Overflow)?
+                / 100;
+            let bet_creator = self
+                .final_total_bet_pool
+                .checked_mul(BC)
+                .ok_or(EscrowError::Overflow)?
+                / 100;
+            payout = payout
+                .checked_add(entry_creator)
+                .ok_or(EscrowError::Overflow)?
+                .checked_add(bet_creator)
+                .ok_or(EscrowError::Overflow)?;
+        }
+
+        // 3) Winning bettors → pro-rata share of 95% bet pool
+        //    (denominators frozen at finalize)
+        let total_winner_bets = self.final_winner_bet_pool;
+        if winner_bet > 0 && total_winner_bets > 0 {
+            let bet_payout_pool = self
+                .final_total_bet_pool
+                .checked_mul(BW)
+                .ok_or(EscrowError::Overflow)?
+                / 100;
+            let user_share = (bet_payout_pool as u128)
+                .checked_mul(winner_bet as u128)
+                .ok_or(EscrowError::Overflow)?
+                / (total_winner_bets as u128);
+            payout = payout
+                .checked_add(user_share as u64)
+                .ok_or(EscrowError::Overflow)?;
+        }
+
+        Ok(payout)
+    }
+
+    /// Bet pool the `claim` formula divides: frozen at finalize, live before.
+    pub fn settlement_bet_pool(&self) -> u64 {
+        if self.finalized { self.final_total_bet_pool } else { self.total_bet_pool }
+    }
+
+    /// Market rows for agents `[start, start + MARKET_PAGE)`. The multiple is
+    /// what 1 lamport on that agent returns if it wins, using the same
+    /// `BW` share and pro-rata split as `claim`; 0 when nobody backs it.
+    pub fn betting_market(&self, start: usize) -> Vec<MarketEntry> {
+        let pool = self.settlement_bet_pool() as u128;
+        let payout_pool = pool * BW as u128 / 100;
+        let end = self.agent_ids.len().min(start.saturating_add(MARKET_PAGE));
+        (start.min(end)..end)
+            .map(|i| {
+                let bet_pool = self.agent_bet_pools[i];
+                let multiple = if bet_pool == 0 {
+                    0
+                } else {
+                    (payout_pool * MULTIPLE_SCALE as u128 / bet_pool as u128)
+                        .min(u64::MAX as u128) as u64
+                };
+                MarketEntry {
+                    agent_id: self.agent_ids[i],
+                    bet_pool,
+                    implied_payout_multiple: multiple,
+                    withdrawn: self.withdrawn[i],
+                }
+            })
+            .collect()
+    }
+
     /// Compute space for init — worst case with `max` agents.
     pub fn space(max: usize) -> usize {
         8                          // anchor discriminator
         + 32                       // creator
         + 32                       // platform
+        + 32                       // pending_platform
         + 32                       // challenge_id
         + 8                        // entry_fee
         + 8                        // start_time
@@ -206,9 +436,16 @@ impl Challenge {
         + 8                        // refund_duration
         + 8                        // total_entry_pool
         + 8                        // total_bet_pool
+        + 8                        // total_refunded
+        + 8                        // vault_reserve
+        + 8                        // final_total_bet_pool
+        + 8                        // final_winner_bet_pool
         + 4                        // agent_count
+        + 4                        // min_agents
         + 1                        // finalized
         + 1                        // cancelled
+        + 1                        // revealed
+        + 1                        // closed
         + 1                        // winner_index
         + 1                        // bump
         + 1                        // vault_bump
@@ -224,22 +461,97 @@ impl Challenge {
         self.agent_ids.iter().position(|id| id == agent_id)
     }
 
+    /// Entry fee a cancelled `claim` refunds to `owner`: none if they never
+    /// enrolled, or if their agent withdrew (withdraw already refunded it).
+    pub fn entry_refund(&self, owner: &Pubkey) -> u64 {
+        match self.agent_owners.iter().position(|o| o == owner) {
+            Some(i) if !self.withdrawn[i] => self.entry_fee,
+            _ => 0,
+        }
+    }
+
     /// Count active (non-withdrawn) agents.
     pub fn active_agent_count(&self) -> u32 {
         self.withdrawn.iter().filter(|&&w| !w).count() as u32
     }
 
-    /// Return winner agent_id (panics if no agents).
-    pub fn winner_agent_id(&self) -> [u8; 32] {
-        self.agent_ids[self.winner_index as usize]
+    /// Index `finalize` picks as the winner: the non-withdrawn agent with
+    /// the most votes, the earliest on a tie. None if every agent withdrew.
+    pub fn pick_winner(&self) -> Option<usize> {
+        let mut winner: Option<(usize, u64)> = None;
+        for (i, &v) in self.vote_counts.iter().enumerate() {
+            if self.withdrawn[i] {
+                continue;
+            }
+            let leads = match winner {
+                Some((_, max_votes)) => v > max_votes,
+                None => true,
+            };
+            if leads {
+                winner = Some((i, v));
+            }
+        }
+        winner.map(|(i, _)| i)
+    }
+
+    /// Return winner agent_id. Errors rather than indexing out of bounds if
+    /// the append-only invariant on the agent arrays has been broken.
+    pub fn winner_agent_id(&self) -> Result<[u8; 32]> {
+        self.agent_ids
+            .get(self.winner_index as usize)
+            .copied()
+            .ok_or(error!(EscrowError::WinnerOutOfBounds))
+    }
+
+    /// Winner agent_id for PDA seeds; zeroes (matches no record) if unset.
+    pub fn winner_seed(&self) -> [u8; 32] {
+        self.agent_ids
+            .get(self.winner_index as usize)
+            .copied()
+            .unwrap_or_default()
     }
 
-    /// Return winner owner.
-    pub fn winner_owner(&self) -> Pubkey {
-        self.agent_owners[self.winner_index as usize]
+    /// Return winner owner. Same bounds check as `winner_agent_id`.
+    pub fn winner_owner(&self) -> Result<Pubkey> {
+        self.agent_owners
+            .get(self.winner_index as usize)
+            .copied()
+            .ok_or(error!(EscrowError::WinnerOutOfBounds))
     }
 }
 
+/// Scalar summary of a `Challenge`, returned by `get_challenge_header` so
+/// status pollers don't pull every per-agent array over RPC.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ChallengeHeader {
+    pub creator: Pubkey,
+    pub platform: Pubkey,
+    pub entry_fee: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub judge_end: i64,
+    pub total_entry_pool: u64,
+    pub total_bet_pool: u64,
+    pub agent_count: u32,
+    pub active_agent_count: u32,
+    pub min_agents: u32,
+    pub finalized: bool,
+    pub cancelled: bool,
+    pub revealed: bool,
+    pub closed: bool,
+    pub winner_index: u8,
+}
+
+/// One agent's row in `get_betting_market`. `implied_payout_multiple` is
+/// scaled by `MULTIPLE_SCALE`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MarketEntry {
+    pub agent_id: [u8; 32],
+    pub bet_pool: u64,
+    pub implied_payout_multiple: u64,
+    pub withdrawn: bool,
+}
+
 /// Proves a user enrolled in a specific challenge.
 #[account]
 pub struct EnrollRecord {
@@ -292,6 +604,40 @@ impl ClaimRecord {
     pub const SPACE: usize = 8 + 1;
 }
 
+/// Program-wide floors, one per deployment. `create` snapshots
+/// `min_agents` into each challenge; `min_fee` / `min_vote_balance` are
+/// read live by `create` / `vote`.
+#[account]
+pub struct Config {
+    pub admin: Pubkey,         // 32
+    pub min_fee: u64,          // 8
+    pub min_agents: u32,       // 4
+    pub min_vote_balance: u64, // 8
+    pub bump: u8,              // 1
+}
+
+impl Config {
+    pub const SPACE: usize = 8 + 32 + 8 + 4 + 8 + 1;
+
+    /// Apply new floors; zero selects the compiled default.
+    pub fn set(&mut self, min_fee: u64, min_agents: u32, min_vote_balance: u64) -> Result<()> {
+        let min_agents = if min_agents == 0 { DEFAULT_MIN_AGENTS } else { min_agents };
+        // Same range `create` enforces, so the default is always usable
+        require!(
+            min_agents >= 2 && min_agents as usize <= MAX_AGENTS,
+            EscrowError::BadConfig
+        );
+        self.min_fee = if min_fee == 0 { DEFAULT_MIN_FEE } else { min_fee };
+        self.min_agents = min_agents;
+        self.min_vote_balance = if min_vote_balance == 0 {
+            DEFAULT_MIN_VOTE_BALANCE
+        } else {
+            min_vote_balance
+        };
+        Ok(())
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // CONTEXTS (Account Validation)
 // ═══════════════════════════════════════════════════════════════════════
@@ -314,6 +660,9 @@ pub struct Create<'info> {
     /// CHECK: platform address stored, validated as non-zero off-chain
     pub platform: UncheckedAccount<'info>,
 
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
     #[account(
         init,
         payer = creator,
@@ -426,6 +775,9 @@ pub struct CastVote<'info> {
     )]
     pub challenge: Account<'info, Challenge>,
 
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
     /// Vote record — init proves first vote.
     #[account(
         init,
@@ -515,16 +867,70 @@ pub struct Claim<'info> {
     )]
     pub claim_record: Account<'info, ClaimRecord>,
 
-    /// EnrollRecord — optional. If it exists, the user enrolled.
+    /// EnrollRecord — optional. If it exists, the user enrolled and didn't
+    /// withdraw (withdraw closes it). Closed to the claimant on a cancelled
+    /// refund.
+    #[account(
+        mut,
+        seeds = [ENROLL_SEED, &challenge_id, claimant.key().as_ref()],
+        bump,
+    )]
     pub enroll_record: Option<Account<'info, EnrollRecord>>,
 
-    /// BetRecord for the winner agent — optional.
+    /// Claimant's own BetRecord on the winner agent — optional.
+    #[account(
+        seeds = [BET_SEED, &challenge_id, claimant.key().as_ref(), &challenge.winner_seed()],
+        bump = winner_bet_record.bump,
+    )]
     pub winner_bet_record: Option<Account<'info, BetRecord>>,
 
-    /// UserBetTotal — optional. Needed for cancelled refunds.
+    /// UserBetTotal — optional. Needed for cancelled refunds, then closed.
+    #[account(
+        mut,
+        seeds = [USER_BET_TOTAL_SEED, &challenge_id, claimant.key().as_ref()],
+        bump,
+    )]
     pub user_bet_total: Option<Account<'info, UserBetTotal>>,
 
     pub system_program: Program<'info, System>,
+    // remaining_accounts (cancelled only): the claimant's BetRecords, one per `bet_agent_ids`
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: [u8; 32])]
+pub struct CloseRecords<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [CHALLENGE_SEED, &challenge_id],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// Claim record — proves the refund was already paid out.
+    #[account(
+        seeds = [CLAIM_RECORD_SEED, &challenge_id, owner.key().as_ref()],
+        bump = claim_record.bump,
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [ENROLL_SEED, &challenge_id, owner.key().as_ref()],
+        bump,
+    )]
+    pub enroll_record: Option<Account<'info, EnrollRecord>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [USER_BET_TOTAL_SEED, &challenge_id, owner.key().as_ref()],
+        bump,
+    )]
+    pub user_bet_total: Option<Account<'info, UserBetTotal>>,
+    // remaining_accounts: the owner's BetRecords, one per `bet_agent_ids`
 }
 
 #[derive(Accounts)]
@@ -555,9 +961,12 @@ pub struct Withdraw<'info> {
     )]
     pub platform: UncheckedAccount<'info>,
 
-    /// EnrollRecord PDA — proves the caller enrolled.
+    /// EnrollRecord PDA — proves the caller enrolled. Closed, rent back to
+    /// the caller, so a later cancelled claim can't refund the entry again.
     /// CHECK: Must be the correct PDA for this caller + challenge.
     #[account(
+        mut,
+        close = caller,
         seeds = [ENROLL_SEED, &challenge_id, caller.key().as_ref()],
         bump = enroll_record.bump,
     )]
@@ -566,6 +975,216 @@ pub struct Withdraw<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(challenge_id: [u8; 32])]
+pub struct CloseCancelled<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Kept as a tombstone (not closed), so `create` can never re-init this
+    /// challenge_id and revive its leftover per-user records.
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [CHALLENGE_SEED, &challenge_id],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// CHECK: Vault PDA — drained to zero.
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, &challenge_id],
+        bump = challenge.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: Must match challenge.platform — receives unclaimed refunds and dust.
+    #[account(
+        mut,
+        constraint = platform.key() == challenge.platform
+    )]
+    pub platform: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    /// Must be the program's upgrade authority, so the deploy can't be
+    /// front-run by whoever calls first.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::ChampionshipEscrow>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ EscrowError::NotAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Config::SPACE,
+        seeds = [CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: [u8; 32])]
+pub struct ReadChallenge<'info> {
+    #[account(
+        seeds = [CHALLENGE_SEED, &challenge_id],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: [u8; 32])]
+pub struct ReleaseVoteStake<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [CHALLENGE_SEED, &challenge_id],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// Vote record — closed, lamports returned to the voter.
+    #[account(
+        mut,
+        close = voter,
+        seeds = [VOTE_RECORD_SEED, &challenge_id, voter.key().as_ref()],
+        bump = vote_record.bump,
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: [u8; 32])]
+pub struct TransferRole<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, &challenge_id],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: [u8; 32])]
+pub struct Reveal<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator @ EscrowError::NotCreator,
+        seeds = [CHALLENGE_SEED, &challenge_id],
+        bump = challenge.bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// VAULT PAYOUTS
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Whether `amount` can leave a vault holding `balance`. The runtime rejects
+/// a transfer that leaves a System account with lamports but below its
+/// rent-exempt minimum, so a payout must drain the vault exactly or leave
+/// at least `rent_min` behind.
+pub fn check_vault_payout(balance: u64, amount: u64, rent_min: u64) -> Result<()> {
+    let left = balance
+        .checked_sub(amount)
+        .ok_or(error!(EscrowError::InsufficientVault))?;
+    require!(left == 0 || left >= rent_min, EscrowError::VaultBelowRent);
+    Ok(())
+}
+
+/// The one routine that moves funds out of a challenge vault. The vault is
+/// a System-owned PDA, so the program can't debit it directly: it signs a
+/// System Program transfer with the vault's seeds. An SPL payout would sign
+/// a token transfer with the same seeds here.
+pub fn pay_from_vault<'info>(
+    system_program: &Program<'info, System>,
+    vault: &SystemAccount<'info>,
+    to: &AccountInfo<'info>,
+    challenge_id: &[u8; 32],
+    vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    check_vault_payout(vault.lamports(), amount, Rent::get()?.minimum_balance(0))?;
+
+    let bump = [vault_bump];
+    let seeds: &[&[u8]] = &[VAULT_SEED, challenge_id, &bump];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: vault.to_account_info(),
+                to: to.clone(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
+
+/// Close a user's BetRecords passed as remaining accounts, `records[i]`
+/// being the record for `agent_ids[i]`. Each must be `owner`'s BET_SEED PDA
+/// for this challenge. Returns the rent sent back to `owner`.
+pub fn close_bet_records<'info>(
+    records: &'info [AccountInfo<'info>],
+    agent_ids: &[[u8; 32]],
+    challenge_id: &[u8; 32],
+    owner: &AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<u64> {
+    require!(records.len() == agent_ids.len(), EscrowError::InvalidRecord);
+
+    let mut lamports: u64 = 0;
+    for (info, agent_id) in records.iter().zip(agent_ids.iter()) {
+        let record: Account<'info, BetRecord> = Account::try_from(info)?;
+        let expected = Pubkey::create_program_address(
+            &[
+                BET_SEED,
+                challenge_id,
+                owner.key.as_ref(),
+                agent_id,
+                &[record.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| error!(EscrowError::InvalidRecord))?;
+        require_keys_eq!(expected, *info.key, EscrowError::InvalidRecord);
+
+        lamports += info.lamports();
+        record.close(owner.clone())?;
+    }
+    Ok(lamports)
+}
 
 // ═══════════════════════════════════════════════════════════════════════
 // PROGRAM LOGIC
@@ -585,8 +1204,15 @@ pub mod championship_escrow {
         challenge_hash: [u8; 32],
         competition_duration: i64,
         refund_duration: i64,
+        min_agents: u32,
     ) -> Result<()> {
-        require!(entry_fee >= MIN_FEE, EscrowError::FeeTooLow);
+        require!(entry_fee >= ctx.accounts.config.min_fee, EscrowError::FeeTooLow);
+        // 0 = program default from Config; otherwise a per-format quorum
+        let min_agents = if min_agents == 0 { ctx.accounts.config.min_agents } else { min_agents };
+        require!(
+            min_agents >= 2 && min_agents as usize <= MAX_AGENTS,
+            EscrowError::BadMinAgents
+        );
 
         let now = Clock::get()?.unix_timestamp;
         require!(start_time > now, EscrowError::BadTimestamps);
@@ -598,6 +1224,7 @@ pub mod championship_escrow {
         let ch = &mut ctx.accounts.challenge;
         ch.creator = ctx.accounts.creator.key();
         ch.platform = ctx.accounts.platform.key();
+        ch.pending_platform = Pubkey::default();
         ch.challenge_id = challenge_id;
         ch.entry_fee = entry_fee;
         ch.start_time = start_time;
@@ -608,9 +1235,16 @@ pub mod championship_escrow {
         ch.refund_duration = refund_duration;
         ch.total_entry_pool = 0;
         ch.total_bet_pool = 0;
+        ch.total_refunded = 0;
+        ch.vault_reserve = 0;
+        ch.final_total_bet_pool = 0;
+        ch.final_winner_bet_pool = 0;
         ch.agent_count = 0;
+        ch.min_agents = min_agents;
         ch.finalized = false;
         ch.cancelled = false;
+        ch.revealed = false;
+        ch.closed = false;
         ch.winner_index = 0;
         ch.bump = ctx.bumps.challenge;
         ch.vault_bump = ctx.bumps.vault;
@@ -620,6 +1254,24 @@ pub mod championship_escrow {
         ch.agent_bet_pools = Vec::with_capacity(MAX_AGENTS);
         ch.withdrawn = Vec::with_capacity(MAX_AGENTS);
 
+        // The creator funds the vault's rent-exempt minimum up front, so no
+        // payout is ever the one that has to leave it behind
+        let reserve = Rent::get()?.minimum_balance(0);
+        let missing = reserve.saturating_sub(ctx.accounts.vault.lamports());
+        if missing > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.vault.to_account_info(),
+                    },
+                ),
+                missing,
+            )?;
+        }
+        ch.vault_reserve = missing;
+
         emit!(ChallengeCreated {
             challenge_id,
             creator: ch.creator,
@@ -630,6 +1282,10 @@ pub mod championship_escrow {
             challenge_hash,
             competition_duration,
             refund_duration,
+            max_agents: MAX_AGENTS as u32,
+            min_agents: ch.min_agents,
+            entry_split: [EW as u8, EC as u8, EP as u8],
+            bet_split: [BW as u8, BC as u8, BP as u8],
         });
 
         Ok(())
@@ -644,8 +1300,13 @@ pub mod championship_escrow {
         let ch = &mut ctx.accounts.challenge;
         let now = Clock::get()?.unix_timestamp;
 
-        require!(now <= ch.start_time, EscrowError::EnrollmentEnded);
+        // All-zero reads like uninitialized data — never a real agent
+        require!(agent_id != [0u8; 32], EscrowError::InvalidAgentId);
+
+        // Cancelled first: a challenge cancelled before start_time must
+        // report Cancelled, not a phase error, to an enroll that lands late.
         require!(!ch.cancelled, EscrowError::Cancelled);
+        require!(now <= ch.start_time, EscrowError::EnrollmentEnded);
         require!(
             (ch.agent_count as usize) < MAX_AGENTS,
             EscrowError::MaxAgents
@@ -704,6 +1365,7 @@ pub mod championship_escrow {
         let now = Clock::get()?.unix_timestamp;
 
         require!(!ch.cancelled && !ch.finalized, EscrowError::NotActive);
+        require!(ch.revealed, EscrowError::NotRevealed);
         require!(
             now > ch.start_time && now <= ch.end_time,
             EscrowError::WrongPhase
@@ -771,7 +1433,8 @@ pub mod championship_escrow {
         let now = Clock::get()?.unix_timestamp;
 
         require!(!ch.cancelled && !ch.finalized, EscrowError::NotActive);
-        require!(ch.active_agent_count() >= MIN_AGENTS, EscrowError::TooFewAgents);
+        require!(ch.revealed, EscrowError::NotRevealed);
+        require!(ch.active_agent_count() >= ch.min_agents, EscrowError::TooFewAgents);
         require!(
             now > ch.end_time && now <= ch.judge_end,
             EscrowError::WrongPhase
@@ -786,7 +1449,7 @@ pub mod championship_escrow {
         // Balance gate
         let voter_lamports = ctx.accounts.voter.lamports();
         require!(
-            voter_lamports >= MIN_VOTE_BALANCE,
+            voter_lamports >= ctx.accounts.config.min_vote_balance,
             EscrowError::LowBalance
         );
 
@@ -816,7 +1479,12 @@ pub mod championship_escrow {
 
         require!(!ch.finalized && !ch.cancelled, EscrowError::NotActive);
         require!(now > ch.start_time, EscrowError::NotEnded);
-        require!(ch.active_agent_count() < MIN_AGENTS, EscrowError::CannotCancel);
+        // Below quorum, or the creator let end_time pass without revealing:
+        // nothing could be bet or voted, so everyone is refunded instead
+        require!(
+            ch.active_agent_count() < ch.min_agents || (!ch.revealed && now > ch.end_time),
+            EscrowError::CannotCancel
+        );
 
         ch.cancelled = true;
 
@@ -834,27 +1502,20 @@ pub mod championship_escrow {
         let now = Clock::get()?.unix_timestamp;
 
         require!(!ch.finalized && !ch.cancelled, EscrowError::NotActive);
+        require!(ch.revealed, EscrowError::NotRevealed);
         require!(now > ch.judge_end, EscrowError::NotEnded);
-        require!(ch.active_agent_count() >= MIN_AGENTS, EscrowError::TooFewAgents);
+        require!(ch.active_agent_count() >= ch.min_agents, EscrowError::TooFewAgents);
 
         // Determine winner: non-withdrawn agent with most votes
-        let mut winner_idx: usize = 0;
-        let mut max_votes: u64 = 0;
-        let mut found_active = false;
-        for (i, &v) in ch.vote_counts.iter().enumerate() {
-            if ch.withdrawn[i] {
-                continue;
-            }
-            if !found_active || v > max_votes {
-                max_votes = v;
-                winner_idx = i;
-                found_active = true;
-            }
-        }
+        let winner_idx = ch.pick_winner().ok_or(EscrowError::TooFewAgents)?;
 
         ch.winner_index = winner_idx as u8;
         ch.finalized = true;
 
+        // Snapshot the bet-payout denominators for claim
+        ch.final_total_bet_pool = ch.total_bet_pool;
+        ch.final_winner_bet_pool = ch.agent_bet_pools[winner_idx];
+
         // Compute platform fee from remaining pools
         let entry_platform = ch
             .total_entry_pool
@@ -870,19 +1531,17 @@ pub mod championship_escrow {
             .checked_add(bet_platform)
             .ok_or(EscrowError::Overflow)?;
 
-        if platform_fee > 0 {
-            let vault_balance = ctx.accounts.vault.lamports();
-            require!(
-                vault_balance >= platform_fee,
-                EscrowError::InsufficientVault
-            );
-
-            **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= platform_fee;
-            **ctx.accounts.platform.to_account_info().try_borrow_mut_lamports()? += platform_fee;
-        }
+        pay_from_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.platform.to_account_info(),
+            &challenge_id,
+            ch.vault_bump,
+            platform_fee,
+        )?;
 
-        let winner_agent_id = ch.agent_ids[winner_idx];
-        let winner_owner = ch.agent_owners[winner_idx];
+        let winner_agent_id = ch.winner_agent_id()?;
+        let winner_owner = ch.winner_owner()?;
 
         emit!(ChallengeFinalized {
             challenge_id,
@@ -896,9 +1555,16 @@ pub mod championship_escrow {
 
     // ─── 7. CLAIM ────────────────────────────────────────────────────
     /// Handles both finalized payouts and cancellation refunds.
-    pub fn claim(
-        ctx: Context<Claim>,
+    /// The winner-owner, creator and winning-bettor shares are independent
+    /// checks that add up, so a creator who entered (and backed) the winning
+    /// agent collects every share in this single claim.
+    /// On a cancelled refund the claimant's BetRecords may be passed as
+    /// remaining accounts (one per `bet_agent_ids`, as in `close_records`)
+    /// and are closed along with the other records.
+    pub fn claim<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Claim<'info>>,
         challenge_id: [u8; 32],
+        bet_agent_ids: Vec<[u8; 32]>,
     ) -> Result<()> {
         let ch = &ctx.accounts.challenge;
 
@@ -906,6 +1572,9 @@ pub mod championship_escrow {
             ch.finalized || ch.cancelled,
             EscrowError::NotDone
         );
+        // Past close_cancelled the vault is drained; unclaimed refunds went
+        // to the platform
+        require!(!ch.closed, EscrowError::NotActive);
 
         let cr = &mut ctx.accounts.claim_record;
         cr.bump = ctx.bumps.claim_record;
@@ -914,10 +1583,10 @@ pub mod championship_escrow {
         let mut payout: u64 = 0;
 
         if ch.cancelled {
-            // Refund entry fee if enrolled (and not already withdrawn)
+            // Refund entry fee if enrolled and not withdrawn
             if ctx.accounts.enroll_record.is_some() {
                 payout = payout
-                    .checked_add(ch.entry_fee)
+                    .checked_add(ch.entry_refund(&claimant))
                     .ok_or(EscrowError::Overflow)?;
             }
 
@@ -928,82 +1597,49 @@ pub mod championship_escrow {
                     .ok_or(EscrowError::Overflow)?;
             }
         } else {
-            let winner_idx = ch.winner_index as usize;
-            let winner_owner = ch.agent_owners[winner_idx];
-
-            // 1) Winner agent owner → 95% of entry pool
-            if claimant == winner_owner {
-                let entry_winner = ch
-                    .total_entry_pool
-                    .checked_mul(EW)
-                    .ok_or(EscrowError::Overflow)?
-                    / 100;
-                payout = payout
-                    .checked_add(entry_winner)
-                    .ok_or(EscrowError::Overflow)?;
-            }
-
-            // 2) Creator → 4% entry + 2% bets
-            if claimant == ch.creator {
-                let entry_creator = ch
-                    .total_entry_pool
-                    .checked_mul(EC)
-                    .ok_or(EscrowError::Overflow)?
-                    / 100;
-                let bet_creator = ch
-                    .total_bet_pool
-                    .checked_mul(BC)
-                    .ok_or(EscrowError::Overflow)?
-                    / 100;
-                payout = payout
-                    .checked_add(entry_creator)
-                    .ok_or(EscrowError::Overflow)?
-                    .checked_add(bet_creator)
-                    .ok_or(EscrowError::Overflow)?;
-            }
-
-            // 3) Winning bettors → pro-rata share of 95% bet pool
-            if let Some(ref wbr) = ctx.accounts.winner_bet_record {
-                let user_bet_on_winner = wbr.amount;
-                if user_bet_on_winner > 0 {
-                    let total_winner_bets =
-                        ch.agent_bet_pools[winner_idx];
-                    if total_winner_bets > 0 {
-                        let bet_payout_pool = ch
-                            .total_bet_pool
-                            .checked_mul(BW)
-                            .ok_or(EscrowError::Overflow)?
-                            / 100;
-                        let user_share = (bet_payout_pool as u128)
-                            .checked_mul(user_bet_on_winner as u128)
-                            .ok_or(EscrowError::Overflow)?
-                            / (total_winner_bets as u128);
-                        payout = payout
-                            .checked_add(user_share as u64)
-                            .ok_or(EscrowError::Overflow)?;
-                    }
-                }
-            }
+            let winner_bet = ctx
+                .accounts
+                .winner_bet_record
+                .as_ref()
+                .map_or(0, |wbr| wbr.amount);
+            payout = ch.finalized_payout(&claimant, winner_bet)?;
         }
 
         require!(payout > 0, EscrowError::NoPayout);
 
-        let vault_balance = ctx.accounts.vault.lamports();
-        require!(
-            vault_balance >= payout,
-            EscrowError::InsufficientVault
-        );
+        pay_from_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.claimant.to_account_info(),
+            &challenge_id,
+            ch.vault_bump,
+            payout,
+        )?;
 
-        **ctx
-            .accounts
-            .vault
-            .to_account_info()
-            .try_borrow_mut_lamports()? -= payout;
-        **ctx
-            .accounts
-            .claimant
-            .to_account_info()
-            .try_borrow_mut_lamports()? += payout;
+        // Cancelled refunds: count toward close_cancelled's "all paid"
+        // check, and are final — so return the records' rent too
+        if ch.cancelled {
+            let ch = &mut ctx.accounts.challenge;
+            ch.total_refunded = ch
+                .total_refunded
+                .checked_add(payout)
+                .ok_or(EscrowError::Overflow)?;
+
+            let dest = ctx.accounts.claimant.to_account_info();
+            if let Some(ref er) = ctx.accounts.enroll_record {
+                er.close(dest.clone())?;
+            }
+            if let Some(ref ubt) = ctx.accounts.user_bet_total {
+                ubt.close(dest.clone())?;
+            }
+            close_bet_records(
+                ctx.remaining_accounts,
+                &bet_agent_ids,
+                &challenge_id,
+                &dest,
+                ctx.program_id,
+            )?;
+        }
 
         emit!(PayoutClaimed {
             challenge_id,
@@ -1057,22 +1693,23 @@ pub mod championship_escrow {
             .ok_or(EscrowError::Overflow)?
             / 100;
 
-        // Verify vault has enough
-        let total_withdraw = refund_amount
-            .checked_add(peek_fee)
-            .ok_or(EscrowError::Overflow)?;
-        let vault_balance = ctx.accounts.vault.lamports();
-        require!(
-            vault_balance >= total_withdraw,
-            EscrowError::InsufficientVault
-        );
-
-        // Transfer refund to caller
-        **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= total_withdraw;
-        **ctx.accounts.caller.to_account_info().try_borrow_mut_lamports()? += refund_amount;
-
-        // Transfer peek fee to platform
-        **ctx.accounts.platform.to_account_info().try_borrow_mut_lamports()? += peek_fee;
+        // Refund to caller, peek fee to platform
+        pay_from_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.caller.to_account_info(),
+            &challenge_id,
+            ch.vault_bump,
+            refund_amount,
+        )?;
+        pay_from_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.platform.to_account_info(),
+            &challenge_id,
+            ch.vault_bump,
+            peek_fee,
+        )?;
 
         // Decrement total_entry_pool by full entry fee
         ch.total_entry_pool = ch
@@ -1090,4 +1727,629 @@ pub mod championship_escrow {
 
         Ok(())
     }
+
+    // ─── 9. RELEASE VOTE STAKE ───────────────────────────────────────
+    /// Return the lamports a voter locked in their VoteRecord by closing it.
+    /// Only once judging is over, so a vote can't be released and re-cast
+    /// within the same voting window.
+    pub fn release_vote_stake(
+        ctx: Context<ReleaseVoteStake>,
+        challenge_id: [u8; 32],
+    ) -> Result<()> {
+        let ch = &ctx.accounts.challenge;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            now > ch.judge_end || ch.finalized || ch.cancelled,
+            EscrowError::NotEnded
+        );
+
+        let amount = ctx.accounts.vote_record.to_account_info().lamports();
+
+        emit!(VoteStakeReleased {
+            challenge_id,
+            voter: ctx.accounts.voter.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    // ─── 10. ROLE TRANSFERS ──────────────────────────────────────────
+    /// Hand the creator role (and its payout share) to another key.
+    /// Only while the challenge is live, so the creator share can't be
+    /// claimed once by the old key and again by the new one.
+    pub fn transfer_creator(
+        ctx: Context<TransferRole>,
+        challenge_id: [u8; 32],
+        new_creator: Pubkey,
+    ) -> Result<()> {
+        let ch = &mut ctx.accounts.challenge;
+
+        require!(!ch.finalized && !ch.cancelled, EscrowError::NotActive);
+        require!(
+            ctx.accounts.caller.key() == ch.creator,
+            EscrowError::NotCreator
+        );
+
+        let previous = ch.creator;
+        ch.creator = new_creator;
+
+        emit!(CreatorTransferred {
+            challenge_id,
+            previous,
+            creator: new_creator,
+        });
+
+        Ok(())
+    }
+
+    /// Step 1 of a platform rotation: current platform nominates a successor.
+    pub fn transfer_platform(
+        ctx: Context<TransferRole>,
+        challenge_id: [u8; 32],
+        new_platform: Pubkey,
+    ) -> Result<()> {
+        let ch = &mut ctx.accounts.challenge;
+
+        require!(
+            ctx.accounts.caller.key() == ch.platform,
+            EscrowError::NotPlatform
+        );
+
+        ch.pending_platform = new_platform;
+
+        emit!(PlatformTransferProposed {
+            challenge_id,
+            platform: ch.platform,
+            pending: new_platform,
+        });
+
+        Ok(())
+    }
+
+    /// Step 2: the nominee signs to take over as fee recipient.
+    pub fn accept_platform(
+        ctx: Context<TransferRole>,
+        challenge_id: [u8; 32],
+    ) -> Result<()> {
+        let ch = &mut ctx.accounts.challenge;
+
+        require!(
+            ch.pending_platform != Pubkey::default()
+                && ctx.accounts.caller.key() == ch.pending_platform,
+            EscrowError::NotPendingPlatform
+        );
+
+        let previous = ch.platform;
+        ch.platform = ch.pending_platform;
+        ch.pending_platform = Pubkey::default();
+
+        emit!(PlatformTransferred {
+            challenge_id,
+            previous,
+            platform: ch.platform,
+        });
+
+        Ok(())
+    }
+
+    // ─── 11. VIEWS ───────────────────────────────────────────────────
+    /// Read-only: returns the scalar header via return data. Call with
+    /// `simulateTransaction` (or Anchor `.view()`).
+    pub fn get_challenge_header(
+        ctx: Context<ReadChallenge>,
+        _challenge_id: [u8; 32],
+    ) -> Result<ChallengeHeader> {
+        Ok(ctx.accounts.challenge.header())
+    }
+
+    /// Read-only: per-agent bet pools and implied payout multiples, paged
+    /// from agent index `start` (`MARKET_PAGE` rows per call to stay under
+    /// the return-data limit). Odds use the `claim` settlement math.
+    pub fn get_betting_market(
+        ctx: Context<ReadChallenge>,
+        _challenge_id: [u8; 32],
+        start: u32,
+    ) -> Result<Vec<MarketEntry>> {
+        Ok(ctx.accounts.challenge.betting_market(start as usize))
+    }
+
+    // ─── 12. CLOSE RECORDS ───────────────────────────────────────────
+    /// Reclaim rent from a cancelled challenge's per-user records after the
+    /// refund was claimed (covers claims made before `claim` closed them).
+    /// BetRecords are passed as remaining accounts, `remaining[i]` being
+    /// the record for `bet_agent_ids[i]`.
+    pub fn close_records<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseRecords<'info>>,
+        challenge_id: [u8; 32],
+        bet_agent_ids: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require!(ctx.accounts.challenge.cancelled, EscrowError::WrongPhase);
+
+        let owner = ctx.accounts.owner.to_account_info();
+        let mut lamports: u64 = 0;
+        if let Some(ref er) = ctx.accounts.enroll_record {
+            lamports += er.to_account_info().lamports();
+        }
+        if let Some(ref ubt) = ctx.accounts.user_bet_total {
+            lamports += ubt.to_account_info().lamports();
+        }
+
+        lamports += close_bet_records(
+            ctx.remaining_accounts,
+            &bet_agent_ids,
+            &challenge_id,
+            &owner,
+            ctx.program_id,
+        )?;
+
+        emit!(RecordsClosed {
+            challenge_id,
+            owner: owner.key(),
+            lamports,
+        });
+
+        Ok(())
+    }
+
+    // ─── 13. CONFIG ──────────────────────────────────────────────────
+    /// One-time setup of the program-wide floors. Only the program's upgrade
+    /// authority may call it, and it becomes the config admin. Pass 0 for
+    /// any value to use its default.
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        min_fee: u64,
+        min_agents: u32,
+        min_vote_balance: u64,
+    ) -> Result<()> {
+        let cfg = &mut ctx.accounts.config;
+        cfg.admin = ctx.accounts.admin.key();
+        cfg.bump = ctx.bumps.config;
+        cfg.set(min_fee, min_agents, min_vote_balance)?;
+
+        emit!(ConfigUpdated {
+            admin: cfg.admin,
+            min_fee: cfg.min_fee,
+            min_agents: cfg.min_agents,
+            min_vote_balance: cfg.min_vote_balance,
+        });
+
+        Ok(())
+    }
+
+    /// Admin retunes the floors. Existing challenges keep the
+    /// `min_agents` they were created with.
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        min_fee: u64,
+        min_agents: u32,
+        min_vote_balance: u64,
+    ) -> Result<()> {
+        let cfg = &mut ctx.accounts.config;
+        require!(
+            ctx.accounts.admin.key() == cfg.admin,
+            EscrowError::NotAdmin
+        );
+        cfg.set(min_fee, min_agents, min_vote_balance)?;
+
+        emit!(ConfigUpdated {
+            admin: cfg.admin,
+            min_fee: cfg.min_fee,
+            min_agents: cfg.min_agents,
+            min_vote_balance: cfg.min_vote_balance,
+        });
+
+        Ok(())
+    }
+
+    // ─── 14. CLOSE CANCELLED ─────────────────────────────────────────
+    /// Creator drains a cancelled challenge's vault once every refund has
+    /// been paid (or CLOSE_GRACE_SECS after judge_end, whichever comes
+    /// first). The creator gets back the rent-exempt reserve they funded at
+    /// create; refunds still unclaimed after the grace period, and any dust,
+    /// go to the platform. The Challenge account stays as a closed tombstone.
+    pub fn close_cancelled(
+        ctx: Context<CloseCancelled>,
+        challenge_id: [u8; 32],
+    ) -> Result<()> {
+        let ch = &mut ctx.accounts.challenge;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(ch.cancelled, EscrowError::WrongPhase);
+        require!(!ch.closed, EscrowError::NotActive);
+        let outstanding = ch
+            .total_entry_pool
+            .checked_add(ch.total_bet_pool)
+            .ok_or(EscrowError::Overflow)?
+            .saturating_sub(ch.total_refunded);
+        require!(
+            outstanding == 0 || now > ch.judge_end.saturating_add(CLOSE_GRACE_SECS),
+            EscrowError::RefundsOutstanding
+        );
+
+        let (reserve, unclaimed, dust) =
+            ch.close_split(ctx.accounts.vault.lamports(), outstanding);
+
+        // Platform first: that leaves exactly the reserve, which the
+        // creator's payout then drains to zero
+        pay_from_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.platform.to_account_info(),
+            &challenge_id,
+            ch.vault_bump,
+            unclaimed + dust,
+        )?;
+        pay_from_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.creator.to_account_info(),
+            &challenge_id,
+            ch.vault_bump,
+            reserve,
+        )?;
+
+        ch.closed = true;
+
+        emit!(ChallengeClosed {
+            challenge_id,
+            creator_rent: reserve,
+            unclaimed_refunds: unclaimed,
+            platform_dust: dust,
+        });
+
+        Ok(())
+    }
+
+    // ─── 15. REVEAL ──────────────────────────────────────────────────
+    /// Creator publishes the full challenge once enrollment closes. It must
+    /// hash to the `challenge_hash` committed at create; `bet` and `vote`
+    /// reject with `NotRevealed` until this lands. Unrevealed at end_time,
+    /// the challenge can only be cancelled.
+    pub fn reveal(
+        ctx: Context<Reveal>,
+        challenge_id: [u8; 32],
+        full_challenge: Vec<u8>,
+    ) -> Result<()> {
+        let ch = &mut ctx.accounts.challenge;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(!ch.finalized && !ch.cancelled, EscrowError::NotActive);
+        require!(
+            !ch.revealed && now > ch.start_time && now <= ch.end_time,
+            EscrowError::WrongPhase
+        );
+        require!(
+            hash(&full_challenge).to_bytes() == ch.challenge_hash,
+            EscrowError::BadReveal
+        );
+
+        ch.revealed = true;
+
+        emit!(ChallengeRevealed {
+            challenge_id,
+            revealed_at: now,
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Rent::minimum_balance(0) at the default rent
+    const RENT_MIN: u64 = 890_880;
+
+    fn challenge(bet_pools: &[u64]) -> Challenge {
+        let n = bet_pools.len();
+        Challenge {
+            creator: Pubkey::new_unique(),
+            platform: Pubkey::new_unique(),
+            pending_platform: Pubkey::default(),
+            challenge_id: [7u8; 32],
+            entry_fee: DEFAULT_MIN_FEE,
+            start_time: 1_000,
+            end_time: 2_000,
+            judge_end: 3_000,
+            challenge_hash: [0u8; 32],
+            competition_duration: 600,
+            refund_duration: 600,
+            total_entry_pool: DEFAULT_MIN_FEE * n as u64,
+            total_bet_pool: bet_pools.iter().sum(),
+            total_refunded: 0,
+            vault_reserve: RENT_MIN,
+            final_total_bet_pool: 0,
+            final_winner_bet_pool: 0,
+            agent_count: n as u32,
+            min_agents: DEFAULT_MIN_AGENTS,
+            finalized: false,
+            cancelled: false,
+            revealed: false,
+            closed: false,
+            winner_index: 0,
+            bump: 255,
+            vault_bump: 255,
+            agent_ids: (0..n).map(|i| [i as u8 + 1; 32]).collect(),
+            agent_owners: (0..n).map(|_| Pubkey::new_unique()).collect(),
+            vote_counts: vec![0; n],
+            agent_bet_pools: bet_pools.to_vec(),
+            withdrawn: vec![false; n],
+        }
+    }
+
+    fn config() -> Config {
+        Config {
+            admin: Pubkey::new_unique(),
+            min_fee: 0,
+            min_agents: 0,
+            min_vote_balance: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_config_set_zero_selects_defaults() {
+        let mut cfg = config();
+        cfg.set(0, 0, 0).unwrap();
+        assert_eq!(cfg.min_fee, DEFAULT_MIN_FEE);
+        assert_eq!(cfg.min_agents, DEFAULT_MIN_AGENTS);
+        assert_eq!(cfg.min_vote_balance, DEFAULT_MIN_VOTE_BALANCE);
+    }
+
+    #[test]
+    fn test_config_set_min_agents_range_matches_create() {
+        let mut cfg = config();
+        cfg.set(0, 2, 0).unwrap();
+        assert_eq!(cfg.min_agents, 2);
+        cfg.set(0, MAX_AGENTS as u32, 0).unwrap();
+        assert_eq!(cfg.min_agents, MAX_AGENTS as u32);
+
+        assert!(cfg.set(0, 1, 0).is_err());
+        assert!(cfg.set(0, MAX_AGENTS as u32 + 1, 0).is_err());
+        // A rejected set leaves the previous floors in place
+        assert_eq!(cfg.min_agents, MAX_AGENTS as u32);
+    }
+
+    #[test]
+    fn test_vault_payout_exact_drain() {
+        check_vault_payout(RENT_MIN + 500, RENT_MIN + 500, RENT_MIN).unwrap();
+        check_vault_payout(0, 0, RENT_MIN).unwrap();
+    }
+
+    #[test]
+    fn test_vault_payout_leaves_reserve() {
+        check_vault_payout(RENT_MIN + 500, 500, RENT_MIN).unwrap();
+        // Rounding dust on top of the reserve is fine
+        check_vault_payout(RENT_MIN + 500, 499, RENT_MIN).unwrap();
+    }
+
+    #[test]
+    fn test_vault_payout_rejects_dust_below_rent() {
+        let err = check_vault_payout(RENT_MIN + 500, RENT_MIN + 499, RENT_MIN).unwrap_err();
+        assert_eq!(err, error!(EscrowError::VaultBelowRent));
+        let err = check_vault_payout(500, 1, RENT_MIN).unwrap_err();
+        assert_eq!(err, error!(EscrowError::VaultBelowRent));
+    }
+
+    #[test]
+    fn test_vault_payout_insufficient() {
+        let err = check_vault_payout(500, 501, RENT_MIN).unwrap_err();
+        assert_eq!(err, error!(EscrowError::InsufficientVault));
+    }
+
+    #[test]
+    fn test_settlement_bet_pool_frozen_at_finalize() {
+        let mut ch = challenge(&[100, 300]);
+        assert_eq!(ch.settlement_bet_pool(), 400);
+
+        ch.finalized = true;
+        ch.final_total_bet_pool = 400;
+        ch.total_bet_pool = 900;
+        assert_eq!(ch.settlement_bet_pool(), 400);
+    }
+
+    #[test]
+    fn test_betting_market_multiples() {
+        let mut ch = challenge(&[100, 0, 300]);
+        ch.withdrawn[1] = true;
+        let market = ch.betting_market(0);
+
+        // 95% of 400 = 380 paid out; multiples scaled by MULTIPLE_SCALE
+        assert_eq!(market.len(), 3);
+        assert_eq!(market[0].agent_id, ch.agent_ids[0]);
+        assert_eq!(market[0].bet_pool, 100);
+        assert_eq!(market[0].implied_payout_multiple, 38_000);
+        assert_eq!(market[1].implied_payout_multiple, 0);
+        assert!(market[1].withdrawn);
+        assert_eq!(market[2].implied_payout_multiple, 12_666);
+    }
+
+    #[test]
+    fn test_betting_market_pages() {
+        let ch = challenge(&[1; MARKET_PAGE + 4]);
+        assert_eq!(ch.betting_market(0).len(), MARKET_PAGE);
+
+        let tail = ch.betting_market(MARKET_PAGE);
+        assert_eq!(tail.len(), 4);
+        assert_eq!(tail[0].agent_id, ch.agent_ids[MARKET_PAGE]);
+
+        assert!(ch.betting_market(MARKET_PAGE + 4).is_empty());
+        assert!(ch.betting_market(usize::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_header_counts_active_agents() {
+        let mut ch = challenge(&[5, 0, 0]);
+        ch.withdrawn[2] = true;
+        ch.revealed = true;
+        let h = ch.header();
+
+        assert_eq!(h.creator, ch.creator);
+        assert_eq!(h.total_bet_pool, 5);
+        assert_eq!(h.agent_count, 3);
+        assert_eq!(h.active_agent_count, 2);
+        assert_eq!(h.min_agents, DEFAULT_MIN_AGENTS);
+        assert!(h.revealed);
+        assert!(!h.finalized && !h.cancelled && !h.closed);
+
+        ch.closed = true;
+        assert!(ch.header().closed);
+    }
+
+    #[test]
+    fn test_cancelled_lifecycle_drains_vault() {
+        // Create funds the reserve; two agents enroll against a quorum of 3
+        let mut ch = challenge(&[0, 0]);
+        let mut vault = ch.vault_reserve + ch.total_entry_pool;
+        assert!(ch.active_agent_count() < ch.min_agents);
+        ch.cancelled = true;
+
+        // Every enrollee claims their refund
+        for _ in 0..ch.agent_ids.len() {
+            check_vault_payout(vault, ch.entry_fee, RENT_MIN).unwrap();
+            vault -= ch.entry_fee;
+            ch.total_refunded += ch.entry_fee;
+        }
+        let outstanding = (ch.total_entry_pool + ch.total_bet_pool) - ch.total_refunded;
+        assert_eq!(outstanding, 0);
+
+        // Close: the creator gets back exactly what they funded
+        let (creator, unclaimed, dust) = ch.close_split(vault, outstanding);
+        assert_eq!((creator, unclaimed, dust), (RENT_MIN, 0, 0));
+        check_vault_payout(vault, unclaimed + dust, RENT_MIN).unwrap();
+        vault -= unclaimed + dust;
+        check_vault_payout(vault, creator, RENT_MIN).unwrap();
+        vault -= creator;
+        assert_eq!(vault, 0);
+    }
+
+    #[test]
+    fn test_entry_refund_skips_withdrawn() {
+        let mut ch = challenge(&[0, 0]);
+        ch.withdrawn[1] = true;
+        assert_eq!(ch.entry_refund(&ch.agent_owners[0]), ch.entry_fee);
+        assert_eq!(ch.entry_refund(&ch.agent_owners[1]), 0);
+        assert_eq!(ch.entry_refund(&Pubkey::new_unique()), 0);
+    }
+
+    #[test]
+    fn test_withdraw_then_cancel_refunds_once() {
+        let mut ch = challenge(&[0, 0, 0]);
+        ch.min_agents = 3;
+        let mut vault = ch.vault_reserve + ch.total_entry_pool;
+
+        // Agent 1 withdraws: 98% back, 2% peek fee, pool shrinks by the fee
+        ch.withdrawn[1] = true;
+        check_vault_payout(vault, ch.entry_fee, RENT_MIN).unwrap();
+        vault -= ch.entry_fee;
+        ch.total_entry_pool -= ch.entry_fee;
+
+        // Now below quorum: cancelled, and every enrollee claims
+        assert!(ch.active_agent_count() < ch.min_agents);
+        ch.cancelled = true;
+        for owner in ch.agent_owners.clone() {
+            let refund = ch.entry_refund(&owner);
+            check_vault_payout(vault, refund, RENT_MIN).unwrap();
+            vault -= refund;
+            ch.total_refunded += refund;
+        }
+
+        // Refunds match the pool exactly, and only the reserve is left
+        assert_eq!(ch.total_refunded, ch.total_entry_pool);
+        assert_eq!(ch.close_split(vault, 0), (RENT_MIN, 0, 0));
+    }
+
+    #[test]
+    fn test_close_split_unclaimed_and_dust_to_platform() {
+        let ch = challenge(&[0, 0]);
+        // One refund never claimed, plus 7 lamports sent to the vault directly
+        let vault = ch.vault_reserve + ch.entry_fee + 7;
+        let (creator, unclaimed, dust) = ch.close_split(vault, ch.entry_fee);
+        assert_eq!(creator, RENT_MIN);
+        assert_eq!(unclaimed, ch.entry_fee);
+        assert_eq!(dust, 7);
+        check_vault_payout(vault, unclaimed + dust, RENT_MIN).unwrap();
+        check_vault_payout(vault - unclaimed - dust, creator, RENT_MIN).unwrap();
+    }
+
+    #[test]
+    fn test_finalized_payout_creator_is_winner() {
+        let mut ch = challenge(&[100, 300]);
+        ch.agent_owners[0] = ch.creator;
+        ch.winner_index = 0;
+        ch.finalized = true;
+        ch.final_total_bet_pool = 400;
+        ch.final_winner_bet_pool = 100;
+        let entry_pool = ch.total_entry_pool;
+
+        // Owner's 95% + creator's 4% of entries, creator's 2% of bets, and
+        // 40 of the winner's 100 bet → 40% of the 95% bet payout pool
+        let creator = ch.creator;
+        let payout = ch.finalized_payout(&creator, 40).unwrap();
+        assert_eq!(payout, entry_pool * 95 / 100 + entry_pool * 4 / 100 + 8 + 152);
+        // Without the winner BetRecord only the first three shares are paid
+        assert_eq!(ch.finalized_payout(&creator, 0).unwrap(), payout - 152);
+
+        // Another backer of the winner, and someone with no claim at all
+        assert_eq!(ch.finalized_payout(&Pubkey::new_unique(), 60).unwrap(), 228);
+        assert_eq!(ch.finalized_payout(&Pubkey::new_unique(), 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_winner_lookups() {
+        let mut ch = challenge(&[0, 0]);
+        ch.winner_index = 1;
+        assert_eq!(ch.winner_agent_id().unwrap(), ch.agent_ids[1]);
+        assert_eq!(ch.winner_owner().unwrap(), ch.agent_owners[1]);
+        assert_eq!(ch.winner_seed(), ch.agent_ids[1]);
+    }
+
+    #[test]
+    fn test_winner_seed_zero_when_unset() {
+        let ch = challenge(&[]);
+        assert_eq!(ch.winner_seed(), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_winner_agent_id_out_of_bounds() {
+        let mut ch = challenge(&[0, 0]);
+        ch.winner_index = 2;
+        let err = ch.winner_agent_id().unwrap_err();
+        assert_eq!(err, error!(EscrowError::WinnerOutOfBounds));
+    }
+
+    #[test]
+    fn test_winner_owner_out_of_bounds() {
+        let mut ch = challenge(&[0, 0]);
+        ch.winner_index = 2;
+        let err = ch.winner_owner().unwrap_err();
+        assert_eq!(err, error!(EscrowError::WinnerOutOfBounds));
+    }
+
+    #[test]
+    fn test_pick_winner_skips_early_withdrawn_agent() {
+        let mut ch = challenge(&[0, 0, 0]);
+        ch.vote_counts = vec![9, 2, 3];
+        ch.withdrawn[0] = true;
+        assert_eq!(ch.pick_winner(), Some(2));
+
+        // No votes at all: the first agent still in, not index 0
+        ch.vote_counts = vec![0, 0, 0];
+        assert_eq!(ch.pick_winner(), Some(1));
+
+        // Finalize records the pick; lookups resolve to the active agent
+        ch.winner_index = ch.pick_winner().unwrap() as u8;
+        assert_eq!(ch.winner_agent_id().unwrap(), ch.agent_ids[1]);
+        assert_eq!(ch.winner_owner().unwrap(), ch.agent_owners[1]);
+    }
+
+    #[test]
+    fn test_pick_winner_none_when_all_withdrawn() {
+        let mut ch = challenge(&[0, 0]);
+        ch.withdrawn = vec![true, true];
+        assert_eq!(ch.pick_winner(), None);
+    }
 }
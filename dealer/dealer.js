@@ -63,15 +63,15 @@ const supabase = createClient(SUPABASE_URL, SUPABASE_SERVICE_ROLE_KEY);
 // CONTRACT ABI
 // ============================================================================
 const ABI = [
-  "function deal(uint32 tableId, bytes32 deckHash) external",
+  "function deal(uint32 tableId, bytes32 deckHash) external returns (uint8 firstToAct, uint8 activeCount, uint32 handNumber)",
   "function action(uint32 tableId, uint8 action, uint256 amount) external",
   "function resolveHand(uint32 tableId, uint8 winningSeat) external",
   "function endSession(uint32 tableId) external",
   "function getTableInfo(uint32 tableId) external view returns (address creator, uint256 prizePool, uint256 buyIn, uint8 maxAgents, uint8 agentCount, uint32 sessionLength, uint32 currentHand, uint64 betDeadline, uint8 state)",
-  "function getAgentInfo(uint32 tableId, uint8 seat) external view returns (address agent, uint256 chips, bool folded, bool kicked, uint8 missedTurns)",
+  "function getAgentInfo(uint32 tableId, uint8 seat) external view returns (address agent, uint256 chips, bool folded, bool kicked, uint8 missedTurns, uint256 rebought)",
   "event TableCreated(uint32 indexed tableId)",
   "event SessionEnded(uint32 indexed tableId)",
-  "event HandResolved(uint32 indexed tableId, uint32 handNumber, uint8 winningSeat)",
+  "event HandResolved(uint32 indexed tableId, uint32 handNumber, uint8 winningSeat, uint8 resolution, uint256 netWon, uint256 amount, uint256 chipsAfter)",
   "event AgentKicked(uint32 indexed tableId, uint8 seat, address agent)",
 ];
 